@@ -0,0 +1,2 @@
+-- Add migration script here
+ALTER TABLE rank_table ADD COLUMN perks text;
@@ -0,0 +1,10 @@
+-- Add migration script here
+CREATE TABLE user_word_count (
+    guild_id bigint NOT NULL,
+    user_id bigint NOT NULL,
+    word_count bigint NOT NULL,
+    PRIMARY KEY (guild_id, user_id),
+    CONSTRAINT guild_id_positive CHECK (guild_id > 0),
+    CONSTRAINT user_id_positive CHECK (user_id > 0),
+    CONSTRAINT word_count_in_range CHECK (word_count >= 0 AND word_count <= 4294967295)
+);
@@ -0,0 +1,5 @@
+-- Add migration script here
+-- Checking this at commit time lets a save move several thresholds past each other
+-- (like doubling every threshold) without tripping over rows that haven't been updated yet.
+ALTER TABLE rank_table DROP CONSTRAINT unique_word_count_per_guild;
+ALTER TABLE rank_table ADD CONSTRAINT unique_word_count_per_guild UNIQUE (guild_id, minimum_word_count) DEFERRABLE INITIALLY DEFERRED;
@@ -0,0 +1,10 @@
+-- Add migration script here
+CREATE TABLE goals (
+    guild_id bigint NOT NULL,
+    user_id bigint NOT NULL,
+    goal bigint NOT NULL,
+    PRIMARY KEY (guild_id, user_id),
+    CONSTRAINT guild_id_positive CHECK (guild_id > 0),
+    CONSTRAINT user_id_positive CHECK (user_id > 0),
+    CONSTRAINT goal_in_range CHECK (goal > 0 AND goal <= 4294967295)
+);
@@ -0,0 +1,14 @@
+-- Add migration script here
+CREATE TABLE word_count_log (
+    id bigserial PRIMARY KEY,
+    guild_id bigint NOT NULL,
+    user_id bigint NOT NULL,
+    word_count bigint NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT guild_id_positive CHECK (guild_id > 0),
+    CONSTRAINT user_id_positive CHECK (user_id > 0),
+    CONSTRAINT word_count_in_range CHECK (word_count >= 0 AND word_count <= 4294967295)
+);
+
+-- History is always looked up for one user, newest first.
+CREATE INDEX word_count_log_user_created_at ON word_count_log (guild_id, user_id, created_at);
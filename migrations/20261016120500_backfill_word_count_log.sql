@@ -0,0 +1,11 @@
+-- Totals from before word_count_log existed have no history, so daily and weekly progress
+-- would count them as written on the day of the next report. Give every user without any
+-- history a starting entry at the Unix epoch, which marks it as from before history was kept.
+-- Users who already have history are left alone, since their total from before it started is unknown.
+INSERT INTO word_count_log (guild_id, user_id, word_count, created_at)
+SELECT guild_id, user_id, word_count, to_timestamp(0)
+FROM user_word_count
+WHERE NOT EXISTS (
+    SELECT 1 FROM word_count_log
+    WHERE word_count_log.guild_id = user_word_count.guild_id AND word_count_log.user_id = user_word_count.user_id
+);
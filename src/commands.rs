@@ -1,18 +1,32 @@
 //! Defines Discord slash commands
 
 use anyhow::anyhow;
-use poise::serenity_prelude::model::guild;
+use poise::serenity_prelude::Mentionable;
 use poise::serenity_prelude::Role;
 use poise::serenity_prelude as serenity;
 use poise::Command;
 use anyhow::Error;
 use anyhow::Result;
 
+use crate::rank::apply_rank_role_changes;
+use crate::rank::parse_rank_import;
 use crate::rank::DiscordRank;
+use crate::rank::MissingBaseRankError;
 use crate::rank::Rank;
-use crate::rank::RankId;
 use crate::rank::RankList;
-use crate::word_count::TotalWordCount;
+use crate::rank::RankRoleChanges;
+use crate::role::interpolate_colors;
+use crate::role::parse_hex_color;
+use crate::role::resolve_role_name;
+use crate::word_count::day_and_week_start;
+use crate::word_count::format_goal_progress;
+use crate::word_count::format_reading_time;
+use crate::word_count::group_thousands;
+use crate::word_count::history_deltas;
+use crate::word_count::UserGoal;
+use crate::word_count::UserWordCount;
+use crate::word_count::words_since;
+use crate::word_count::WordCountArgument;
 
 type Context<'a> = poise::Context<'a, crate::core::GlobalCommandData, anyhow::Error>;
 
@@ -30,24 +44,216 @@ type Context<'a> = poise::Context<'a, crate::core::GlobalCommandData, anyhow::Er
 pub fn get_commands() -> Vec<Command<crate::core::GlobalCommandData, Error>>
 {
     // Release commands go here
-    let mut commands = vec![set_rank(), list_ranks()];
+    let mut commands = vec![report(), set_goal(), clear_goal(), set_rank(), set_rank_by_name(), remove_rank(), remove_rank_by_name(), set_rank_perks(), list_ranks(), validate_ranks(), leaderboard(), profile(), history(), progress(), journey(), reading_time(), relink_rank(), scale_thresholds(), shift_thresholds(), export_ranks(), import_ranks(), gradient_ranks(), about()];
     // Add debug commands if in debug mode
     if cfg!(debug_assertions)
     {
         commands.append(&mut debug::get_debug_commands());
     }
-    return commands;
+    commands
+}
+
+/// Reports your word count. Use +/- (like +500) to add to or subtract from your last total
+#[poise::command(slash_command, guild_only)]
+async fn report(ctx: Context<'_>, word_count: String) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    let argument: WordCountArgument = word_count.parse()?;
+
+    let mut user_word_count = UserWordCount::load(pool, guild_id, ctx.author().id).await?;
+    let old_word_count = user_word_count.word_count();
+    let new_word_count = argument.convert_to_total(old_word_count.word_count());
+    user_word_count.set_word_count(new_word_count);
+    user_word_count.save(pool).await?;
+
+    let ranks = RankList::load(pool, guild_id).await?;
+    let mut response = format!("Updated your word count from {} to {}!", old_word_count, new_word_count);
+    // The count is already saved at this point, so a problem with roles shouldn't fail the whole report.
+    match update_rank_roles(ctx, &ranks, guild_id, new_word_count.word_count()).await
+    {
+        Ok(Some(rank_update)) =>
+        {
+            response.push('\n');
+            response.push_str(&rank_update);
+        }
+        Ok(None) => (),
+        Err(e) => log::warn!("Couldn't update rank roles for {} in guild {}: {:?}", ctx.author().id, guild_id, e),
+    }
+    if let Some(goal) = UserGoal::load(pool, guild_id, ctx.author().id).await?.goal()
+    {
+        response.push_str(&format!("\nGoal: {}", format_goal_progress(new_word_count, goal)));
+    }
+    let next_rank = ranks.get_next_rank(new_word_count.word_count());
+    let words_left = ranks.words_until_next_rank(new_word_count.word_count());
+    if let (Some(next_rank), Some(words_left)) = (next_rank, words_left)
+    {
+        response.push_str(&format!("\nYou need {} more words to reach {}.", group_thousands(words_left.into()), next_rank.rank_id.role_id().mention()));
+    }
+
+    ctx.say(response).await?;
+    Ok(())
+}
+
+/// Gives the author the rank role for `word_count` if it's above their current rank,
+/// and takes away the lower rank roles they have. Members are never demoted.
+/// Roles that aren't ranks are never touched.
+///
+/// Returns a line for the reply if anything changed (or failed to change).
+async fn update_rank_roles(ctx: Context<'_>, ranks: &RankList, guild_id: serenity::GuildId, word_count: u32) -> Result<Option<String>>
+{
+    let mut member = ctx.author_member().await.ok_or(anyhow!("Couldn't find you in this server!"))?.into_owned();
+
+    let mut changes = ranks.rank_role_changes(&member.roles, word_count);
+    if let Some(role_id) = changes.add
+    {
+        // Skip ranks whose role has been deleted, there's nothing to give.
+        // Their old rank roles are kept too, so they aren't left with no rank at all.
+        let guild = ctx.partial_guild().await.ok_or(anyhow!("Couldn't load this server!"))?;
+        if Rank::new(guild_id, role_id, 0).to_rank(&guild).is_none()
+        {
+            log::warn!("Rank role {} in guild {} no longer exists", role_id, guild_id);
+            changes = RankRoleChanges::default();
+        }
+    }
+
+    if changes.add.is_none() && changes.remove.is_empty()
+    {
+        return Ok(None);
+    }
+
+    let failed = apply_rank_role_changes(&mut member, ctx.http(), &changes).await;
+    if !failed.is_empty()
+    {
+        for (role_id, e) in failed
+        {
+            log::warn!("Couldn't update rank role {} for {}: {}", role_id, member.user.id, e);
+        }
+        return Ok(Some("I couldn't update your rank roles, ask an admin to check my permissions.".to_string()));
+    }
+    Ok(changes.add.map(|role_id| format!("You reached {}!", role_id.mention())))
+}
+
+/// Sets your word count goal. Use +/- (like +5000) to raise or lower your current goal
+#[poise::command(slash_command, guild_only)]
+async fn set_goal(ctx: Context<'_>, goal: String) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    let argument: WordCountArgument = goal.parse()?;
+
+    let mut user_goal = UserGoal::load(pool, guild_id, ctx.author().id).await?;
+    let current_goal = user_goal.goal().map_or(0, |goal| goal.word_count());
+    let new_goal = argument.convert_to_total(current_goal);
+    if new_goal.word_count() == 0
+    {
+        ctx.say("Your goal has to be more than 0 words! Use /clear_goal to remove it.").await?;
+        return Ok(());
+    }
+    user_goal.set_goal(Some(new_goal));
+    user_goal.save(pool).await?;
+
+    let word_count = UserWordCount::load(pool, guild_id, ctx.author().id).await?.word_count();
+    ctx.say(format!("Your goal is now {} words!\nGoal: {}", new_goal, format_goal_progress(word_count, new_goal))).await?;
+    Ok(())
+}
+
+/// Removes your word count goal
+#[poise::command(slash_command, guild_only)]
+async fn clear_goal(ctx: Context<'_>) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    let mut user_goal = UserGoal::load(pool, guild_id, ctx.author().id).await?;
+    if user_goal.goal().is_none()
+    {
+        ctx.say("You don't have a goal set.").await?;
+        return Ok(());
+    }
+    user_goal.set_goal(None);
+    user_goal.save(pool).await?;
+
+    ctx.say("Cleared your goal!").await?;
+    Ok(())
 }
 
 #[poise::command(slash_command, guild_only, default_member_permissions = "ADMINISTRATOR")]
 async fn set_rank(ctx: Context<'_>, role: serenity::Role, minimum_word_count: u32) -> Result<()>
+{
+    let mut response = format!("Added rank {}!", role);
+    if let Some(warning) = save_rank(ctx, role.id, minimum_word_count).await?
+    {
+        response.push_str(&format!("\nWarning: {}", warning));
+    }
+    ctx.say(response).await?;
+    Ok(())
+}
+
+/// Prefix version of set_rank that takes a role name instead of a mention
+///
+/// The name is matched ignoring case, and can be cut short as long as it only matches one role.
+/// Use quotes for names with spaces, like `.setrank "Poet Laureate" 50000`.
+#[poise::command(prefix_command, guild_only, required_permissions = "ADMINISTRATOR", rename = "setrank")]
+async fn set_rank_by_name(ctx: Context<'_>, role_name: String, minimum_word_count: u32) -> Result<()>
+{
+    let role_id = resolve_role(ctx, &role_name).await?;
+    let mut response = format!("Added rank {}!", role_id.mention());
+    if let Some(warning) = save_rank(ctx, role_id, minimum_word_count).await?
+    {
+        response.push_str(&format!("\nWarning: {}", warning));
+    }
+    ctx.say(response).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, guild_only, default_member_permissions = "ADMINISTRATOR")]
+async fn remove_rank(ctx: Context<'_>, role: serenity::Role) -> Result<()>
+{
+    delete_rank(ctx, role.id).await
+}
+
+/// Prefix version of remove_rank that takes a role name instead of a mention
+///
+/// The name is matched the same way as `.setrank`.
+#[poise::command(prefix_command, guild_only, required_permissions = "ADMINISTRATOR", rename = "removerank")]
+async fn remove_rank_by_name(ctx: Context<'_>, role_name: String) -> Result<()>
+{
+    let role_id = resolve_role(ctx, &role_name).await?;
+    delete_rank(ctx, role_id).await
+}
+
+/// Removes the rank for a role, if there is one, and tells the user what happened.
+async fn delete_rank(ctx: Context<'_>, role_id: serenity::RoleId) -> Result<()>
 {
     let pool = ctx.data().get_pool();
     let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
 
     let mut ranks = RankList::load(pool, guild_id).await?;
+    // remove_rank only looks at the role, so the word count doesn't matter.
+    if !ranks.remove_rank(Rank::new(guild_id, role_id, 0))
+    {
+        ctx.say(format!("{} isn't a rank, so nothing was removed.", role_id.mention())).await?;
+        return Ok(());
+    }
+    ranks.save(pool).await?;
 
-    let new_rank = Rank::new(guild_id, role.id, minimum_word_count);
+    ctx.say(format!("Removed rank {}!", role_id.mention())).await?;
+    Ok(())
+}
+
+/// Adds or updates the rank for a role and saves it.
+/// Returns a warning if the ladder is left without a rank at 0 words.
+async fn save_rank(ctx: Context<'_>, role_id: serenity::RoleId, minimum_word_count: u32) -> Result<Option<MissingBaseRankError>>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    let mut ranks = RankList::load(pool, guild_id).await?;
+
+    let new_rank = Rank::new(guild_id, role_id, minimum_word_count);
     let result = ranks.add_rank(new_rank);
     if let Err(err) = result
     {
@@ -55,40 +261,623 @@ async fn set_rank(ctx: Context<'_>, role: serenity::Role, minimum_word_count: u3
         let discord_error = err.to_discord_error(&guild).expect("Unable to get the role from the guild");
         return Err(discord_error.into())
     }
+    let warning = ranks.validate_has_base_rank().err();
+    ranks.save(pool).await?;
+    Ok(warning)
+}
+
+/// Finds the role in this server matching a typed name. See [resolve_role_name].
+async fn resolve_role(ctx: Context<'_>, role_name: &str) -> Result<serenity::RoleId>
+{
+    let guild = ctx.partial_guild().await.ok_or(anyhow!("Couldn't fetch the server"))?;
+    let roles: Vec<(serenity::RoleId, String)> = guild.roles.values()
+        .map(|role| (role.id, role.name.clone()))
+        .collect();
+    Ok(resolve_role_name(role_name, &roles)?)
+}
+
+/// Sets a note about what a rank unlocks, or clears it if no note is given
+///
+/// This is just shown in the rank list, it doesn't change any permissions.
+#[poise::command(slash_command, guild_only, default_member_permissions = "ADMINISTRATOR")]
+async fn set_rank_perks(ctx: Context<'_>, role: serenity::Role, perks: Option<String>) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    let mut ranks = RankList::load(pool, guild_id).await?;
+    let cleared = perks.is_none();
+    if !ranks.set_perks(role.id, perks)
+    {
+        ctx.say(format!("{} isn't a rank!", role)).await?;
+        return Ok(());
+    }
     ranks.save(pool).await?;
 
-    ctx.say(format!("Added rank {}!", role)).await?;
+    if cleared
+    {
+        ctx.say(format!("Cleared the perks for {}!", role)).await?;
+    }
+    else
+    {
+        ctx.say(format!("Updated the perks for {}!", role)).await?;
+    }
     Ok(())
 }
 
 #[poise::command(slash_command, guild_only)]
 async fn list_ranks(ctx: Context<'_>) -> Result<()>
 {
+    const RANKS_PER_PAGE: usize = 15;
+    // Discord's message limit, which also keeps pages well inside an embed description's.
+    const MAX_PAGE_LENGTH: usize = 2000;
+
     let pool = ctx.data().get_pool();
     let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
 
     let ranks = RankList::load(pool, guild_id).await?;
-    let guild = ctx.partial_guild().await.unwrap();
+    if ranks.is_empty()
+    {
+        ctx.say("This server doesn't have any ranks yet! An admin can add some with /set_rank.").await?;
+        return Ok(());
+    }
+    let guild = ctx.partial_guild().await.ok_or(anyhow!("Couldn't fetch the server"))?;
+
+    let mut lines = Vec::with_capacity(ranks.len());
+    for rank in ranks.iter()
+    {
+        let discord_rank: Option<DiscordRank<Role>> = rank.to_rank(&guild);
+        let mut line = match discord_rank
+        {
+            Some(discord_rank) => discord_rank.to_string(),
+            // The role was deleted out from under us, but the rank should still show up so it can be fixed.
+            None => format!("Deleted role {}:\t{} (see /relink_rank)\n", rank.rank_id.role_id(), rank.minimum_word_count),
+        };
+        if let Some(perks) = ranks.perks(rank.rank_id.role_id())
+        {
+            line.push_str(&format!("\tPerks: {}\n", perks));
+        }
+        lines.push(line);
+    }
+
+    let pages = paginate_lines(&lines, RANKS_PER_PAGE, MAX_PAGE_LENGTH);
+    if let [page] = pages.as_slice()
+    {
+        ctx.say(page).await?;
+        return Ok(());
+    }
+
+    let pages: Vec<&str> = pages.iter().map(String::as_str).collect();
+    poise::builtins::paginate(ctx, &pages).await?;
+    Ok(())
+}
+
+/// Joins lines into pages of at most `per_page` lines, starting a new page early
+/// if the next line would push it past `max_length` characters.
+/// A single line longer than `max_length` is cut short so every page fits.
+/// `per_page` and `max_length` are treated as at least 1, so a page is never empty.
+fn paginate_lines(lines: &[String], per_page: usize, max_length: usize) -> Vec<String>
+{
+    let per_page = per_page.max(1);
+    let max_length = max_length.max(1);
+    let mut pages = Vec::new();
+    let mut page = String::new();
+    let mut lines_on_page = 0;
+    for line in lines
+    {
+        let line: String = if line.chars().count() > max_length
+        {
+            line.chars().take(max_length - 1).chain(std::iter::once('…')).collect()
+        }
+        else
+        {
+            line.clone()
+        };
+
+        if lines_on_page > 0 && (lines_on_page == per_page || page.chars().count() + line.chars().count() > max_length)
+        {
+            pages.push(std::mem::take(&mut page));
+            lines_on_page = 0;
+        }
+        page.push_str(&line);
+        lines_on_page += 1;
+    }
+    if !page.is_empty()
+    {
+        pages.push(page);
+    }
+    pages
+}
+
+/// Checks the rank ladder for problems, like deleted roles or roles the bot can't assign
+#[poise::command(slash_command, guild_only, default_member_permissions = "ADMINISTRATOR")]
+async fn validate_ranks(ctx: Context<'_>) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    let ranks = RankList::load(pool, guild_id).await?;
+    let guild = ctx.partial_guild().await.ok_or(anyhow!("Couldn't fetch the server"))?;
+
+    // The bot can only assign roles below its own highest role.
+    let bot_member = guild.member(ctx, ctx.framework().bot_id).await?;
+    let bot_highest_position = bot_member.roles.iter()
+        .filter_map(|role_id| guild.roles.get(role_id))
+        .map(|role| role.position)
+        .max()
+        .unwrap_or(0);
+
+    let problems = ranks.validate(&guild, bot_highest_position);
+    if problems.is_empty()
+    {
+        ctx.say("No problems found with the rank ladder!").await?;
+        return Ok(());
+    }
+
+    let mut response = format!("Found {} problem(s) with the rank ladder:\n", problems.len());
+    for problem in problems
+    {
+        response.push_str(&format!("- {}\n", problem));
+    }
+
+    ctx.say(response).await?;
+    Ok(())
+}
+
+/// Moves a rank from a deleted role to a new role, keeping its word count
+///
+/// The old role is given as an id or mention, since it may not exist anymore.
+#[poise::command(slash_command, guild_only, default_member_permissions = "ADMINISTRATOR")]
+async fn relink_rank(ctx: Context<'_>, old_role_id: String, new_role: serenity::Role) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    let old_role_id = old_role_id.trim();
+    let old_role_id: serenity::RoleId = serenity::utils::parse_role_mention(old_role_id)
+        .or_else(|| old_role_id.parse().ok())
+        .ok_or(anyhow!("{} isn't a role id or mention", old_role_id))?;
+
+    let mut ranks = RankList::load(pool, guild_id).await?;
+    let new_rank = ranks.relink(old_role_id, new_role.id)?;
+    ranks.save(pool).await?;
+
+    ctx.say(format!("Relinked the rank at {} words to {}!", new_rank.minimum_word_count, new_role)).await?;
+    Ok(())
+}
+
+/// Multiplies every rank's word count by a factor, like 0.5 to halve them
+#[poise::command(slash_command, guild_only, default_member_permissions = "ADMINISTRATOR")]
+async fn scale_thresholds(ctx: Context<'_>, factor: f32) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    let mut ranks = RankList::load(pool, guild_id).await?;
+    ranks.scale(factor)?;
+
+    let response = describe_thresholds(&ranks);
+    ranks.save(pool).await?;
+
+    ctx.say(response).await?;
+    Ok(())
+}
+
+/// Lists each rank's role and new word count after a bulk threshold change.
+fn describe_thresholds(ranks: &RankList) -> String
+{
+    let mut response = String::from("Updated the ranks:\n");
+    for rank in ranks.iter()
+    {
+        response.push_str(&format!("{}:\t{}\n", rank.rank_id.role_id().mention(), group_thousands(rank.minimum_word_count.into())));
+    }
+    response
+}
+
+/// Adds a number of words to every rank's word count. Use a negative number to lower them
+#[poise::command(slash_command, guild_only, default_member_permissions = "ADMINISTRATOR")]
+async fn shift_thresholds(ctx: Context<'_>, delta: i32) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    let mut ranks = RankList::load(pool, guild_id).await?;
+    ranks.shift(delta)?;
+
+    let response = describe_thresholds(&ranks);
+    ranks.save(pool).await?;
+
+    ctx.say(response).await?;
+    Ok(())
+}
+
+/// Exports this server's ranks as a JSON file, for backups or moving to another server
+#[poise::command(slash_command, guild_only, default_member_permissions = "ADMINISTRATOR")]
+async fn export_ranks(ctx: Context<'_>) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    let ranks = RankList::load(pool, guild_id).await?;
+    if ranks.is_empty()
+    {
+        ctx.say("This server doesn't have any ranks yet!").await?;
+        return Ok(());
+    }
+
+    let attachment = serenity::CreateAttachment::bytes(ranks.to_json()?.into_bytes(), "ranks.json");
+    ctx.send(poise::CreateReply::default()
+        .content(format!("Exported {} rank(s)!", ranks.len()))
+        .attachment(attachment))
+        .await?;
+    Ok(())
+}
+
+/// Replaces this server's ranks with the ones in a JSON file from /export_ranks
+///
+/// The file is a list of `{"role_id": ..., "minimum_word_count": ..., "perks": ...}` entries,
+/// where `perks` is optional.
+/// Importing replaces every existing rank rather than merging, so the ranks end up exactly as
+/// they are in the file. Nothing is changed if any entry in the file is bad.
+#[poise::command(slash_command, guild_only, default_member_permissions = "ADMINISTRATOR")]
+async fn import_ranks(ctx: Context<'_>, file: serenity::Attachment) -> Result<()>
+{
+    // Way more than any sensible rank list, but small enough that we aren't downloading anything huge.
+    const MAX_FILE_SIZE: u32 = 1024 * 1024;
+
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    if file.size > MAX_FILE_SIZE
+    {
+        ctx.say("That file is too big to be a ranks file!").await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+    let json = String::from_utf8(file.download().await?).map_err(|_| anyhow!("That file isn't a text file!"))?;
+    let guild = ctx.partial_guild().await.ok_or(anyhow!("Couldn't fetch the server"))?;
+    let imported = parse_rank_import(&json, &guild)?;
+
+    let mut ranks = RankList::load(pool, guild_id).await?;
+    ranks.clear();
+    let imported_ranks: Vec<Rank> = imported.iter().map(|(rank, _)| *rank).collect();
+    if let Err(err) = ranks.add_ranks(&imported_ranks)
+    {
+        return Err(match err.to_discord_error(&guild)
+        {
+            Some(discord_error) => discord_error.into(),
+            None => err.into(),
+        });
+    }
+    for (rank, perks) in imported
+    {
+        ranks.set_perks(rank.rank_id.role_id(), perks);
+    }
+    // Count after adding, since a role listed twice only ends up as one rank.
+    let count = ranks.len();
+    ranks.save(pool).await?;
+
+    ctx.say(format!("Imported {} rank(s)!", count)).await?;
+    Ok(())
+}
+
+/// Colors every rank's role along a gradient from the lowest rank to the highest
+///
+/// Colors are hex codes like #ff8800.
+#[poise::command(slash_command, guild_only, default_member_permissions = "ADMINISTRATOR")]
+async fn gradient_ranks(ctx: Context<'_>, from_color: String, to_color: String) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    let from = parse_hex_color(&from_color).ok_or(anyhow!("{} isn't a hex color like #ff8800", from_color))?;
+    let to = parse_hex_color(&to_color).ok_or(anyhow!("{} isn't a hex color like #ff8800", to_color))?;
+
+    let ranks = RankList::load(pool, guild_id).await?;
+    let ranks: Vec<&Rank> = ranks.iter().collect();
+    if ranks.is_empty()
+    {
+        ctx.say("This server doesn't have any ranks yet!").await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+    let colors = interpolate_colors(from, to, ranks.len());
+    let mut failed = Vec::new();
+    for (rank, color) in ranks.iter().zip(colors)
+    {
+        let role_id = rank.rank_id.role_id();
+        if let Err(e) = guild_id.edit_role(ctx, role_id, serenity::EditRole::new().colour(color)).await
+        {
+            log::warn!("Couldn't recolor role {}: {}", role_id, e);
+            failed.push(role_id.mention().to_string());
+        }
+    }
+
+    if failed.is_empty()
+    {
+        ctx.say(format!("Recolored {} rank(s)!", ranks.len())).await?;
+    }
+    else
+    {
+        ctx.say(format!("Recolored {} rank(s), but couldn't recolor {}", ranks.len() - failed.len(), failed.join(", "))).await?;
+    }
+    Ok(())
+}
+
+/// Shows the server's top writers by word count
+#[poise::command(slash_command, guild_only)]
+async fn leaderboard(
+    ctx: Context<'_>,
+    #[description = "How many writers to show (default 10, max 25)"]
+    #[min = 1]
+    #[max = 25]
+    count: Option<u32>,
+) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    // Discord enforces the min and max, but clamp anyway so a huge message can't slip through.
+    let count = count.unwrap_or(10).clamp(1, 25);
+    let top = UserWordCount::top(pool, guild_id, count).await?;
+    if top.is_empty()
+    {
+        ctx.say("No one has reported yet!").await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+    // member() checks the cache before going to the API, and any lookups that do need
+    // a request run at the same time instead of one after another.
+    let members = poise::futures_util::future::join_all(
+        top.iter().map(|entry| guild_id.member(ctx, entry.user_id()))
+    ).await;
 
     let mut response = String::new();
-    let ranks: Vec<DiscordRank<Role>> = ranks.iter().map(|x| x.to_rank(&guild).unwrap()).collect();
+    for (i, (entry, member)) in top.iter().zip(members).enumerate()
+    {
+        // Use display names instead of mentions so the leaderboard doesn't ping everyone on it.
+        let name = match member
+        {
+            Ok(member) => member.display_name().to_string(),
+            Err(_) => format!("Unknown user ({})", entry.user_id()),
+        };
+        response.push_str(&format!("{}. {}: {} words\n", i + 1, name, entry.word_count()));
+    }
 
-    for rank in ranks
-   {
-        response.push_str(&format!("{}", rank));
+    ctx.say(response).await?;
+    Ok(())
+}
+
+/// Shows your (or someone else's) word count and rank
+#[poise::command(slash_command, guild_only)]
+async fn profile(ctx: Context<'_>, user: Option<serenity::User>) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+    let user = user.as_ref().unwrap_or(ctx.author());
+
+    let user_word_count = UserWordCount::load(pool, guild_id, user.id).await?;
+    let total_word_count = user_word_count.word_count();
+    let word_count = total_word_count.word_count();
+    let (day_start, week_start) = day_and_week_start(chrono::Utc::now());
+    let recent = user_word_count.history_since(pool, week_start).await?;
+    let goal = UserGoal::load(pool, guild_id, user.id).await?.goal();
+    let ranks = RankList::load(pool, guild_id).await?;
+    // Below the lowest rank, you don't have a rank yet.
+    let rank = ranks.get_rank_for_word_count(word_count)
+        .filter(|rank| rank.minimum_word_count <= word_count);
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(user.display_name())
+        .thumbnail(user.face())
+        .field("Word count", total_word_count.to_string(), true)
+        .field("Today (UTC)", format!("{:+}", words_since(&recent, day_start)), true)
+        .field("This week (UTC)", format!("{:+}", words_since(&recent, week_start)), true);
+
+    match rank
+    {
+        Some(rank) =>
+        {
+            embed = embed.field("Rank", rank.rank_id.role_id().mention().to_string(), true);
+            let guild = ctx.partial_guild().await.ok_or(anyhow!("Couldn't fetch the server"))?;
+            // Roles without a color have a colour of 0, which would make the embed black.
+            if let Some(colour) = rank.to_rank(&guild).map(|discord_rank| discord_rank.role().colour).filter(|colour| colour.0 != 0)
+            {
+                embed = embed.colour(colour);
+            }
+        }
+        None => embed = embed.field("Rank", "None yet", true),
+    }
+
+    let next_rank = ranks.get_next_rank(word_count);
+    let words_left = ranks.words_until_next_rank(word_count);
+    if let (Some(next_rank), Some(words_left)) = (next_rank, words_left)
+    {
+        embed = embed.field("Next rank", format!("{} more words to reach {}", group_thousands(words_left.into()), next_rank.rank_id.role_id().mention()), false);
+    }
+    else if rank.is_some()
+    {
+        embed = embed.field("Next rank", "Top rank reached!", false);
+    }
+
+    let goal = match goal
+    {
+        Some(goal) => format_goal_progress(total_word_count, goal),
+        None => "No goal set".to_string(),
+    };
+    embed = embed.field("Goal", goal, false);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Shows your (or someone else's) most recent reports
+#[poise::command(slash_command, guild_only)]
+async fn history(
+    ctx: Context<'_>,
+    user: Option<serenity::User>,
+    #[description = "How many reports to show (default 10, max 25)"]
+    #[min = 1]
+    #[max = 25]
+    count: Option<u32>,
+) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+    let user = user.as_ref().unwrap_or(ctx.author());
+
+    let count = count.unwrap_or(10).clamp(1, 25);
+    // Load one extra entry so the oldest one we show still has something to compare against.
+    let mut entries = UserWordCount::load(pool, guild_id, user.id).await?.history(pool, count + 1).await?;
+    if entries.is_empty()
+    {
+        ctx.say(format!("{} hasn't reported yet!", user.display_name())).await?;
+        return Ok(());
+    }
+
+    let deltas = history_deltas(&entries);
+    entries.truncate(count as usize);
+
+    let mut response = format!("Recent reports for {}:\n", user.display_name());
+    for (entry, delta) in entries.iter().zip(deltas)
+    {
+        let timestamp = match entry.is_from_before_history()
+        {
+            true => "Before history was kept".to_string(),
+            false => serenity::FormattedTimestamp::new(entry.created_at().into(), Some(serenity::FormattedTimestampStyle::ShortDateTime)).to_string(),
+        };
+        response.push_str(&format!("{}: {} words", timestamp, entry.word_count()));
+        if let Some(delta) = delta
+        {
+            response.push_str(&format!(" ({:+})", delta));
+        }
+        response.push('\n');
+    }
+
+    ctx.say(response).await?;
+    Ok(())
+}
+
+/// Shows how far you (or someone else) are toward the next rank
+#[poise::command(slash_command, guild_only)]
+async fn progress(ctx: Context<'_>, user: Option<serenity::User>) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+    let user = user.as_ref().unwrap_or(ctx.author());
+
+    let ranks = RankList::load(pool, guild_id).await?;
+    if ranks.is_empty()
+    {
+        ctx.say("This server doesn't have any ranks yet!").await?;
+        return Ok(());
+    }
+
+    let word_count = UserWordCount::load(pool, guild_id, user.id).await?.word_count().word_count();
+    let response = match ranks.band_progress(word_count)
+    {
+        Some(progress) => format!("{} has {} words, {:.0}% of the way toward the next rank.", user.mention(), group_thousands(word_count.into()), (progress * 100.0).floor()),
+        None => format!("{} has {} words and has reached the top rank!", user.mention(), group_thousands(word_count.into())),
+    };
+
+    ctx.say(response).await?;
+    Ok(())
+}
+
+/// Shows every rank you'd pass on the way from 0 words to a target word count
+#[poise::command(slash_command, guild_only)]
+async fn journey(ctx: Context<'_>, target: u32) -> Result<()>
+{
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+    let ranks = RankList::load(pool, guild_id).await?;
+    if ranks.is_empty()
+    {
+        ctx.say("This server doesn't have any ranks yet!").await?;
+        return Ok(());
+    }
+
+    let mut response = String::new();
+    if let Some(first_rank) = ranks.get_rank_for_word_count(0).filter(|rank| rank.minimum_word_count == 0)
+    {
+        response.push_str(&format!("You'd start out as {}.\n", first_rank.rank_id.role_id().mention()));
+    }
+
+    let crossed = ranks.ranks_crossed(0, target);
+    if crossed.is_empty()
+    {
+        response.push_str(&format!("You wouldn't reach any new ranks by {} words.", group_thousands(target.into())));
+    }
+    else
+    {
+        for (i, rank) in crossed.iter().enumerate()
+        {
+            response.push_str(&format!("{}. {} at {} words\n", i + 1, rank.rank_id.role_id().mention(), group_thousands(rank.minimum_word_count.into())));
+        }
     }
 
     ctx.say(response).await?;
     Ok(())
 }
 
+/// Estimates how long it would take to read your (or someone else's) total word count
+#[poise::command(slash_command, guild_only)]
+async fn reading_time(
+    ctx: Context<'_>,
+    user: Option<serenity::User>,
+    #[description = "Reading speed in words per minute (default 250)"]
+    #[min = 1]
+    words_per_minute: Option<u32>,
+) -> Result<()>
+{
+    const DEFAULT_WORDS_PER_MINUTE: u32 = 250;
+
+    let pool = ctx.data().get_pool();
+    let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+    let user = user.as_ref().unwrap_or(ctx.author());
+    let words_per_minute = words_per_minute.unwrap_or(DEFAULT_WORDS_PER_MINUTE);
+
+    let word_count = UserWordCount::load(pool, guild_id, user.id).await?.word_count();
+    let Some(time) = word_count.reading_time(words_per_minute) else
+    {
+        ctx.say("At 0 words per minute, nobody would ever finish!").await?;
+        return Ok(());
+    };
+
+    ctx.say(format!("Reading {}'s {} words at {} words per minute would take {}.", user.display_name(), word_count, words_per_minute, format_reading_time(time))).await?;
+    Ok(())
+}
+
+/// Shows the bot's version and how long it's been running
+#[poise::command(slash_command)]
+async fn about(ctx: Context<'_>) -> Result<()>
+{
+    let embed = serenity::CreateEmbed::new()
+        .title("Presley")
+        .field("Version", env!("CARGO_PKG_VERSION"), true)
+        .field("Uptime", crate::core::format_uptime(ctx.data().uptime()), true)
+        .field("Shards", ctx.cache().shard_count().to_string(), true);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
 #[cfg(debug_assertions)]
 pub mod debug {
     //! Special debug commands that will not be compiled and included in release mode.
     //! These commands are used for checking bot connection, sanity checks, 
     //! or registering slash commands per guild (see [register_commands]).
-    use anyhow::{Error, Result};
+    use anyhow::{anyhow, Error, Result};
     use poise::Command;
+    use poise::serenity_prelude as serenity;
+    use poise::serenity_prelude::Mentionable;
+
+    use crate::rank::Rank;
+    use crate::rank::RankList;
 
     type Context<'a> = poise::Context<'a, crate::core::GlobalCommandData, anyhow::Error>;
 
@@ -100,7 +889,7 @@ pub mod debug {
     ///
     pub fn get_debug_commands() -> Vec<Command<crate::core::GlobalCommandData, Error>>
     {
-        vec![ping(), register_commands(), unregister_commands()]
+        vec![ping(), register_commands(), unregister_commands(), pending_removals()]
     }
     
     /// says "Pong!"
@@ -139,5 +928,80 @@ pub mod debug {
         ctx.say("Unregistered commands").await?;
         Ok(())
     }
+
+    /// Shows which ranks would be deleted on the next save, optionally after removing a role
+    ///
+    /// Nothing is saved, so the pending removals are thrown away once the command finishes.
+    #[poise::command(slash_command, prefix_command, guild_only)]
+    async fn pending_removals(ctx: Context<'_>, role: Option<serenity::Role>) -> Result<()>
+    {
+        let pool = ctx.data().get_pool();
+        let guild_id = ctx.guild_id().ok_or(anyhow!("This command can only be run in a server!"))?;
+
+        let mut ranks = RankList::load(pool, guild_id).await?;
+        if let Some(role) = role
+        {
+            // remove_rank only looks at the role, so the word count doesn't matter.
+            ranks.remove_rank(Rank::new(guild_id, role.id, 0));
+        }
+
+        let mut response = String::from("Pending removals:\n");
+        for rank in ranks.pending_removals()
+        {
+            response.push_str(&format!("{}:\t{}\n", rank.rank_id.role_id().mention(), rank.minimum_word_count));
+        }
+
+        ctx.say(response).await?;
+        Ok(())
+    }
 }
 
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    pub fn paginate_lines_splits_by_line_count()
+    {
+        let lines: Vec<String> = (0..31).map(|i| format!("Rank {}\n", i)).collect();
+        let pages = paginate_lines(&lines, 15, 2000);
+        let lines_per_page: Vec<usize> = pages.iter().map(|page| page.lines().count()).collect();
+        assert_eq!(lines_per_page, vec![15, 15, 1]);
+        assert!(pages[2].starts_with("Rank 30"));
+    }
+
+    #[test]
+    pub fn paginate_lines_starts_a_new_page_before_going_over_length()
+    {
+        let lines: Vec<String> = (0..3).map(|_| format!("{}\n", "a".repeat(799))).collect();
+        let pages = paginate_lines(&lines, 15, 2000);
+        let lines_per_page: Vec<usize> = pages.iter().map(|page| page.lines().count()).collect();
+        assert_eq!(lines_per_page, vec![2, 1]);
+        assert!(pages.iter().all(|page| page.chars().count() <= 2000));
+    }
+
+    #[test]
+    pub fn paginate_lines_cuts_short_a_line_that_is_too_long()
+    {
+        let lines = vec!["a".repeat(2500)];
+        let pages = paginate_lines(&lines, 15, 2000);
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].chars().count(), 2000);
+        assert!(pages[0].ends_with('…'));
+    }
+
+    #[test]
+    pub fn paginate_lines_with_zero_limits()
+    {
+        let lines: Vec<String> = (0..3).map(|i| format!("Rank {}\n", i)).collect();
+        let pages = paginate_lines(&lines, 0, 0);
+        assert_eq!(pages, vec!["…", "…", "…"]);
+    }
+
+    #[test]
+    pub fn paginate_lines_empty()
+    {
+        assert!(paginate_lines(&[], 15, 2000).is_empty());
+    }
+}
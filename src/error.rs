@@ -0,0 +1,105 @@
+//! Command error handling.
+//! Most errors are handed off to poise's default handler, but some errors
+//! (like losing the database connection) get a friendlier reply.
+
+use log::error;
+
+use crate::core::GlobalCommandData;
+
+/// The message users see when a command fails because the database can't be reached.
+pub const DB_UNAVAILABLE_MESSAGE: &str = "The bot's database is temporarily unavailable";
+
+/// Returns true if the [sqlx::Error] means we couldn't talk to the database at all,
+/// as opposed to the query itself being bad or returning nothing.
+///
+/// Postgres reports some connection problems as regular database errors,
+/// so we also check for the "connection exception" (08) SQLSTATE class
+/// and the shutdown codes (57P01-57P03).
+pub fn is_db_unavailable(err: &sqlx::Error) -> bool
+{
+    match err
+    {
+        sqlx::Error::Io(_)
+        | sqlx::Error::Tls(_)
+        | sqlx::Error::PoolTimedOut
+        | sqlx::Error::PoolClosed
+        | sqlx::Error::WorkerCrashed => true,
+        sqlx::Error::Database(db_err) => db_err.code()
+            .is_some_and(|code| code.starts_with("08") || matches!(code.as_ref(), "57P01" | "57P02" | "57P03")),
+        _ => false,
+    }
+}
+
+/// Returns true if any error in the chain is a [sqlx::Error] that [is_db_unavailable].
+fn is_db_unavailable_error(err: &anyhow::Error) -> bool
+{
+    err.chain()
+        .filter_map(|cause| cause.downcast_ref::<sqlx::Error>())
+        .any(is_db_unavailable)
+}
+
+/// Error handler passed into [poise::FrameworkOptions::on_error].
+///
+/// If a command failed because the database is down, we log it and tell the user
+/// instead of dumping the raw sqlx error on them.
+/// Everything else goes to [poise::builtins::on_error].
+pub async fn on_error(error: poise::FrameworkError<'_, GlobalCommandData, anyhow::Error>)
+{
+    match error
+    {
+        poise::FrameworkError::Command { error, ctx, .. } if is_db_unavailable_error(&error) =>
+        {
+            error!("Database unavailable while running command {}: {:?}", ctx.command().qualified_name, error);
+            if let Err(e) = ctx.say(DB_UNAVAILABLE_MESSAGE).await
+            {
+                error!("Failed to send database unavailable message: {}", e);
+            }
+        }
+        error =>
+        {
+            if let Err(e) = poise::builtins::on_error(error).await
+            {
+                error!("Error while handling error: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    pub fn io_error_is_unavailable()
+    {
+        let err = sqlx::Error::Io(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+        assert!(is_db_unavailable(&err));
+    }
+
+    #[test]
+    pub fn pool_errors_are_unavailable()
+    {
+        assert!(is_db_unavailable(&sqlx::Error::PoolTimedOut));
+        assert!(is_db_unavailable(&sqlx::Error::PoolClosed));
+        assert!(is_db_unavailable(&sqlx::Error::WorkerCrashed));
+    }
+
+    #[test]
+    pub fn query_errors_are_not_unavailable()
+    {
+        assert!(!is_db_unavailable(&sqlx::Error::RowNotFound));
+        assert!(!is_db_unavailable(&sqlx::Error::ColumnNotFound("role_id".to_string())));
+        assert!(!is_db_unavailable(&sqlx::Error::Protocol("bad message".to_string())));
+    }
+
+    #[test]
+    pub fn wrapped_anyhow_error_is_unavailable()
+    {
+        let err = anyhow::Error::from(sqlx::Error::PoolTimedOut).context("Loading ranks");
+        assert!(is_db_unavailable_error(&err));
+
+        let err = anyhow::anyhow!("This command can only be run in a server!");
+        assert!(!is_db_unavailable_error(&err));
+    }
+}
@@ -1,6 +1,8 @@
 pub mod word_count;
 pub mod rank;
+pub mod role;
 pub mod mock;
 pub mod commands;
 pub mod core;
+pub mod error;
 
@@ -1,10 +1,10 @@
-use log::warn;
+use log::{info, warn};
 use poise::serenity_prelude as serenity;
 use progress_report_bot::commands;
 use progress_report_bot::core;
+use progress_report_bot::error;
 
 type Result<T> = anyhow::Result<T>;
-type Context<'a> = poise::Context<'a, (), anyhow::Error>;
 
 #[tokio::main]
 async fn main() -> Result<()> 
@@ -18,30 +18,35 @@ async fn main() -> Result<()>
     }
 
     let variables = core::Variables::load_variables()?;
-    let intents = serenity::GatewayIntents::non_privileged() & serenity::GatewayIntents::MESSAGE_CONTENT;
+    let intents = serenity::GatewayIntents::non_privileged() | serenity::GatewayIntents::MESSAGE_CONTENT;
 
     let global_command_data = core::GlobalCommandDataBuilder::new()
                                 .max_connections(variables.max_connections())
                                 .database_url(variables.database_url().to_string())
                                 .build().await?;
+    // Keep our own handle on the pool so it can be closed on shutdown,
+    // since the global data itself gets moved into the framework.
+    let db_pool = global_command_data.get_pool().clone();
 
     let framework = poise::Framework::<core::GlobalCommandData, anyhow::Error>::builder() 
         .options(poise::FrameworkOptions {
             commands: commands::get_commands(),
             prefix_options: poise::PrefixFrameworkOptions {
-                prefix: Some(".".to_string()),
+                prefix: Some(variables.prefix().to_string()),
                 ..Default::default()
             },
+            on_error: |error| Box::pin(error::on_error(error)),
             ..Default::default()
         })
         .setup(move |ctx, _ready, framework| {
+            let mut global_command_data = global_command_data;
             Box::pin(async move {
                 // Register commands globally if in release mode
                 if cfg!(not(debug_assertions))
                 {
                     poise::builtins::register_globally(ctx, &framework.options().commands).await?;
                 }
-                //global_command_data.set_client(&framework.client());
+                global_command_data.set_client(ctx.http.clone());
                 Ok(global_command_data)
             })
         })
@@ -51,6 +56,25 @@ async fn main() -> Result<()>
         .framework(framework)
         .await;
 
-    client?.start().await?;
+    let mut client = client?;
+
+    // Stop the shards on Ctrl-C so start() returns and we can close the pool cleanly,
+    // instead of dying in the middle of a transaction.
+    let shard_manager = client.shard_manager.clone();
+    tokio::spawn(async move {
+        let result = tokio::signal::ctrl_c().await;
+        if let Err(e) = result
+        {
+            warn!("Couldn't listen for Ctrl-C: {e}");
+            return;
+        }
+        info!("Received Ctrl-C, shutting down");
+        shard_manager.shutdown_all().await;
+    });
+
+    client.start().await?;
+
+    // Waits for any in-flight queries to finish before closing every connection.
+    db_pool.close().await;
     Ok(())
 }
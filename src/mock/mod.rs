@@ -1,7 +1,9 @@
 //! This module exposes various traits for interfacing with 
-//! [serenity::Guild], [serenity::Role], and [[serenity::User]
+//! [serenity::Guild], [serenity::Role], and [serenity::Member]
 //! which may be replaced with mock objects as needed for testing
 
+use std::future::Future;
+
 use poise::serenity_prelude as serenity;
 
 /// This trait is used to mock [serenity::Guild].
@@ -14,7 +16,7 @@ pub trait GuildLike<R: RoleLike>
     /// You also get a reference to a [RoleLike], not the [RoleLike] itself.
     /// A [GuildLike] is considered to be the owner of any [RoleLike] associated with itself,
     /// so references to [RoleLike] must last as long as the [GuildLike] being referenced.
-    fn role<'a>(&'a self, role_id: serenity::RoleId) -> Option<&'a R>;
+    fn role(&self, role_id: serenity::RoleId) -> Option<&R>;
 
     fn id(&self) -> serenity::GuildId;
 }
@@ -54,6 +56,10 @@ pub trait RoleLike
 
     /// Gets the [serenity::GuildId] associated with this [RoleLike]
     fn guild_id(&self) -> serenity::GuildId;
+
+    /// Gets the position of this [RoleLike] in the guild's role hierarchy.
+    /// Higher positions are higher up the role list.
+    fn position(&self) -> u16;
 }
 
 impl RoleLike for serenity::Role
@@ -67,5 +73,61 @@ impl RoleLike for serenity::Role
     {
         self.guild_id
     }
+
+    fn position(&self) -> u16
+    {
+        self.position
+    }
 }
 
+
+/// This trait is used to mock [serenity::Member].
+/// It exposes getters for the member's roles, and lets you add or remove them.
+pub trait MemberLike
+{
+    /// Gets the [serenity::UserId] of this member.
+    fn user_id(&self) -> serenity::UserId;
+
+    /// Gets the [serenity::RoleId]s this member currently has.
+    /// You get a reference to the [MemberLike]'s own list, so it lasts as long as the
+    /// [MemberLike] does and includes any roles added or removed through it.
+    fn roles(&self) -> &[serenity::RoleId];
+
+    /// Gives this member a role, using `http` to tell Discord about it.
+    /// The role is only added to [MemberLike::roles] if that works.
+    fn add_role(&mut self, http: &serenity::Http, role_id: serenity::RoleId) -> impl Future<Output = serenity::Result<()>> + Send;
+
+    /// Takes a role away from this member, using `http` to tell Discord about it.
+    /// The role is only removed from [MemberLike::roles] if that works.
+    fn remove_role(&mut self, http: &serenity::Http, role_id: serenity::RoleId) -> impl Future<Output = serenity::Result<()>> + Send;
+}
+
+impl MemberLike for serenity::Member
+{
+    fn user_id(&self) -> serenity::UserId
+    {
+        self.user.id
+    }
+
+    fn roles(&self) -> &[serenity::RoleId]
+    {
+        &self.roles
+    }
+
+    async fn add_role(&mut self, http: &serenity::Http, role_id: serenity::RoleId) -> serenity::Result<()>
+    {
+        serenity::Member::add_role(self, http, role_id).await?;
+        if !self.roles.contains(&role_id)
+        {
+            self.roles.push(role_id);
+        }
+        Ok(())
+    }
+
+    async fn remove_role(&mut self, http: &serenity::Http, role_id: serenity::RoleId) -> serenity::Result<()>
+    {
+        serenity::Member::remove_role(self, http, role_id).await?;
+        self.roles.retain(|id| *id != role_id);
+        Ok(())
+    }
+}
@@ -3,20 +3,26 @@
 //! have a word count of 0, so on and so forth.
 
 use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Display;
 use std::hash::{Hash, Hasher};
 
 use derive_more::From;
 use derive_more::Into;
+use getset::CopyGetters;
 use getset::Getters;
 use poise::serenity_prelude::GuildId;
+use poise::serenity_prelude::Mentionable;
 use poise::serenity_prelude::RoleId;
 use poise::serenity_prelude as serenity;
+use serde::Deserialize;
+use serde::Serialize;
 use sqlx::PgPool;
 use thiserror::Error;
 
 use crate::mock::GuildLike;
+use crate::mock::MemberLike;
 use crate::mock::RoleLike;
 
 /// A DiscordRank is effectively a reference to a [serenity::Role]
@@ -28,10 +34,19 @@ pub struct DiscordRank<'a, T: RoleLike>
     minimum_word_count: u32,
 }
 
+impl<'a, T: RoleLike> DiscordRank<'a, T>
+{
+    /// Gets the role this rank gives.
+    pub fn role(&self) -> &'a T
+    {
+        self.role
+    }
+}
+
 impl Display for DiscordRank<'_, serenity::Role>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}:\t{}\n", self.role, self.minimum_word_count)
+        writeln!(f, "{}:\t{}", self.role, self.minimum_word_count)
     }
 }
 
@@ -63,7 +78,7 @@ impl Eq for Rank {}
 impl PartialOrd for Rank
 {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(&other))
+        Some(self.cmp(other))
     }
 }
 
@@ -150,7 +165,8 @@ impl PartialEq for RankHash
 impl Eq for RankHash {}
 
 /// A minimal version of [Rank] which uses [serenity::RoleId] instead of [serenity::Role]
-#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, CopyGetters)]
+#[getset(get_copy = "pub")]
 pub struct RankId
 {
     guild_id: serenity::GuildId,
@@ -158,19 +174,88 @@ pub struct RankId
 }
 
 /// Internal representation of the database record
+#[derive(Debug, Clone)]
 struct DbRankId
 {
     guild_id: i64,
     role_id: i64,
     minimum_word_count: i32,
+    perks: Option<String>,
+}
+
+
+/// A rank as it's written out by [RankList::to_json], for backing up a guild's ranks.
+/// Ids are written as strings like Discord does, since they're too big for some JSON readers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedRank
+{
+    pub guild_id: serenity::GuildId,
+    pub role_id: serenity::RoleId,
+    pub minimum_word_count: u32,
+    /// Left out of the file when the rank has no perks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub perks: Option<String>,
+}
+
+/// A rank as read by [parse_rank_import]. Only the role and word count are needed,
+/// so anything else in the file (like the guild_id from [ExportedRank]) is ignored.
+#[derive(Debug, Deserialize)]
+struct ImportedRank
+{
+    role_id: serenity::RoleId,
+    minimum_word_count: u32,
+    #[serde(default)]
+    perks: Option<String>,
 }
 
+/// Returned by [parse_rank_import] when a ranks file can't be imported.
+#[derive(Debug, Error)]
+pub enum ImportRanksError
+{
+    #[error("Couldn't read the ranks file: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+    #[error("Role {0} doesn't exist in this server")]
+    UnknownRole(serenity::RoleId),
+    #[error("{0} words is more than can be stored (max {max})", max = i32::MAX)]
+    TooLarge(u32),
+}
+
+/// Reads a JSON array of `{role_id, minimum_word_count, perks}` entries, like the ones written by
+/// [RankList::to_json], and turns them into [Rank]s for `guild`, each with its perks (if it has any).
+/// `perks` can be left out.
+/// The file's own guild ids are ignored, so ranks can be moved from one guild to another
+/// as long as the role ids exist in `guild`.
+///
+/// This doesn't check for ranks sharing a word count, that's left to [RankList::add_ranks].
+///
+/// # Errors
+///
+/// [ImportRanksError::InvalidJson] - The file isn't a JSON array of ranks.
+/// [ImportRanksError::UnknownRole] - A role in the file doesn't exist in `guild`.
+/// [ImportRanksError::TooLarge] - A word count is too big to be stored in the database.
+pub fn parse_rank_import<G: GuildLike<R>, R: RoleLike>(json: &str, guild: &G) -> Result<Vec<(Rank, Option<String>)>, ImportRanksError>
+{
+    let imported: Vec<ImportedRank> = serde_json::from_str(json)?;
+    imported.into_iter()
+        .map(|rank| {
+            if guild.role(rank.role_id).is_none()
+            {
+                return Err(ImportRanksError::UnknownRole(rank.role_id));
+            }
+            if rank.minimum_word_count > i32::MAX as u32
+            {
+                return Err(ImportRanksError::TooLarge(rank.minimum_word_count));
+            }
+            Ok((Rank::new(guild.id(), rank.role_id, rank.minimum_word_count), rank.perks))
+        })
+        .collect()
+}
 
 /// A set of ranks, ordered from lowest to highest threshold.
 /// 
 /// Ideally a rank list should start with one rank at 0, but I don't think I will actually enforce that.
-/// What *will* be enforced is that you can only have a RankList with at LEAST one rank.
-/// This makes ops like [get_rank_for_word_count] infallible.
+/// A RankList can also be empty, like when a guild hasn't set up any ranks yet,
+/// so lookups like [RankList::get_rank_for_word_count] return an [Option].
 #[derive(Getters)]
 pub struct RankList
 {
@@ -183,6 +268,9 @@ pub struct RankList
     // When we remove a rank, we add it to this list so that the next time we save we remove these
     // records.
     pending_removals: HashSet<RankHash>,
+    // Freeform notes about what each rank unlocks. This is just metadata and
+    // lives outside of Rank so it doesn't affect ordering or equality.
+    perks: HashMap<serenity::RoleId, String>,
 }
 
 #[derive(Debug, Error)]
@@ -199,9 +287,121 @@ pub enum AddRankDiscordError
     RankExistsWithWordCount(serenity::Role)
 }
 
+#[derive(Debug, Error)]
+pub enum RelinkRankError
+{
+    #[error("Role {0} isn't a rank")]
+    NotARank(serenity::RoleId),
+    #[error("Role {0} is already a rank")]
+    AlreadyARank(serenity::RoleId),
+}
+
+/// Returned when bulk-adjusting thresholds with [RankList::scale] or [RankList::shift] would break the ladder.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AdjustThresholdsError
+{
+    #[error("The factor must be a number above 0")]
+    InvalidFactor,
+    #[error("More than one rank would end up at {0} words")]
+    Collision(u32),
+    #[error("A rank would need {0} words, which is more than can be stored (max {max})", max = i32::MAX)]
+    TooLarge(u32),
+}
+
+/// Returned by [RankList::validate_has_base_rank] when there's no rank at 0 words.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MissingBaseRankError
+{
+    #[error("There aren't any ranks yet")]
+    NoRanks,
+    #[error("There is no rank at 0 words, so members won't have a rank until they reach {} words", .0.minimum_word_count)]
+    LowestRankAbove(Rank),
+}
+
+/// A problem with a guild's rank ladder, as found by [RankList::validate].
+#[derive(Debug, PartialEq, Eq)]
+pub enum LadderProblem
+{
+    /// There's no rank at 0 words, so members who haven't written much yet won't have a rank.
+    NoZeroRank,
+    /// The rank's role has been deleted from the guild.
+    DeletedRole(Rank),
+    /// The rank's minimum_word_count is too big to be stored in the database.
+    ThresholdTooLarge(Rank),
+    /// The rank's role is at or above the bot's highest role, so the bot can't assign it.
+    CannotManageRole(Rank),
+    /// The second rank needs more than [GAP_FACTOR] times the words of the rank just below it,
+    /// as found by [RankList::find_gaps].
+    Gap(Rank, Rank),
+}
+
+/// How many times bigger a threshold can be than the one below it before [RankList::find_gaps]
+/// calls it a gap.
+pub const GAP_FACTOR: u32 = 10;
+
+impl Display for LadderProblem
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self
+        {
+            LadderProblem::NoZeroRank => write!(f, "There is no rank at 0 words, so new members won't have a rank"),
+            LadderProblem::DeletedRole(rank) => write!(f, "The role for the rank at {} words (id {}) no longer exists", rank.minimum_word_count, rank.rank_id.role_id),
+            LadderProblem::ThresholdTooLarge(rank) => write!(f, "{} requires {} words, which is more than can be stored (max {})", rank.rank_id.role_id.mention(), rank.minimum_word_count, i32::MAX),
+            LadderProblem::CannotManageRole(rank) => write!(f, "{} is above the bot's highest role, so the bot can't assign it", rank.rank_id.role_id.mention()),
+            LadderProblem::Gap(lower, higher) => write!(f, "There's a big gap between {} ({} words) and {} ({} words)", lower.rank_id.role_id.mention(), lower.minimum_word_count, higher.rank_id.role_id.mention(), higher.minimum_word_count),
+        }
+    }
+}
+
+/// The rank roles a member needs added or removed when they reach a new rank,
+/// as worked out by [RankList::rank_role_changes].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RankRoleChanges
+{
+    /// The rank role the member should have but doesn't yet.
+    pub add: Option<RoleId>,
+    /// Rank roles below the new rank that the member has outgrown.
+    pub remove: Vec<RoleId>,
+}
+
+/// Applies [RankRoleChanges] to `member`, adding the new rank role before removing the old ones.
+/// If the new role can't be added, the old ones are left alone so the member isn't left with no rank role.
+/// Otherwise every removal is attempted even if an earlier one fails.
+///
+/// Returns the roles that couldn't be changed along with why, so an empty list means everything worked.
+pub async fn apply_rank_role_changes<M: MemberLike>(member: &mut M, http: &serenity::Http, changes: &RankRoleChanges) -> Vec<(RoleId, serenity::Error)>
+{
+    let mut failed = Vec::new();
+    if let Some(role_id) = changes.add
+    {
+        let result = member.add_role(http, role_id).await;
+        if let Err(e) = result
+        {
+            failed.push((role_id, e));
+            return failed;
+        }
+    }
+    for role_id in changes.remove.iter().copied()
+    {
+        let result = member.remove_role(http, role_id).await;
+        if let Err(e) = result
+        {
+            failed.push((role_id, e));
+        }
+    }
+    failed
+}
+
+/// Returns true if a bot whose highest role is at `bot_highest_position` can assign a role at
+/// `role_position`. Discord only lets you manage roles strictly below your own highest role.
+pub fn can_bot_manage_role(bot_highest_position: u16, role_position: u16) -> bool
+{
+    role_position < bot_highest_position
+}
+
 impl AddRankError
 {
-    pub fn to_discord_error<'a, G: GuildLike<serenity::Role>>(&self, get_role_object: &'a G) -> Option<AddRankDiscordError>
+    pub fn to_discord_error<G: GuildLike<serenity::Role>>(&self, get_role_object: &G) -> Option<AddRankDiscordError>
     {
         match self
         {
@@ -255,17 +455,35 @@ impl RankList
         Ok(())
     }
 
-    pub fn add_ranks(&mut self, ranks: &[Rank])
+    /// Adds each rank in turn with [RankList::add_rank].
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first rank that can't be added and returns its [AddRankError].
+    /// Ranks before it stay added.
+    pub fn add_ranks(&mut self, ranks: &[Rank]) -> Result<(), AddRankError>
     {
         for rank in ranks
         {
-            self.add_rank(*rank);
+            self.add_rank(*rank)?;
         }
+        Ok(())
+    }
+
+    /// Removes every rank, queueing them all for removal like [RankList::remove_rank]
+    /// so the next save deletes them from the database.
+    pub fn clear(&mut self)
+    {
+        self.pending_removals.extend(self.rank_set.drain());
+        self.rank_order.clear();
+        self.perks.clear();
     }
 
     /// Interestingly, we don't care about the minimum_word_count here.
     /// We just use the guild_id and role_id.
-    pub fn remove_rank(&mut self, rank: Rank)
+    ///
+    /// Returns true if the rank was in the list and is now queued for removal.
+    pub fn remove_rank(&mut self, rank: Rank) -> bool
     {
         let take_rank = self.rank_set.take(&rank.into());
 
@@ -274,13 +492,154 @@ impl RankList
         {
             self.rank_order.remove(&rank.into());
             self.pending_removals.insert(rank);
+            return true;
+        }
+        false
+    }
+
+    /// Points the rank for `old_role_id` at `new_role_id` instead, keeping its minimum_word_count.
+    /// This is for when a rank's role was deleted and recreated, so the threshold isn't lost.
+    /// The old rank is queued for removal like with [RankList::remove_rank].
+    ///
+    /// # Errors
+    ///
+    /// [RelinkRankError::NotARank] - `old_role_id` isn't a rank in this list.
+    /// [RelinkRankError::AlreadyARank] - `new_role_id` is already a rank,
+    /// so relinking would clobber its threshold.
+    pub fn relink(&mut self, old_role_id: serenity::RoleId, new_role_id: serenity::RoleId) -> Result<Rank, RelinkRankError>
+    {
+        let guild_id = self.guild_id.ok_or(RelinkRankError::NotARank(old_role_id))?;
+
+        if self.contains_role(new_role_id)
+        {
+            return Err(RelinkRankError::AlreadyARank(new_role_id));
+        }
+
+        let old_rank = self.rank_set.take(&Rank::new(guild_id, old_role_id, 0).into())
+            .ok_or(RelinkRankError::NotARank(old_role_id))?;
+        self.rank_order.remove(&old_rank.0);
+        self.pending_removals.insert(old_rank);
+
+        let new_rank = Rank::new(guild_id, new_role_id, old_rank.0.minimum_word_count);
+        if let Some(perks) = self.perks.remove(&old_role_id)
+        {
+            self.perks.insert(new_role_id, perks);
+        }
+        self.rank_set.insert(new_rank.into());
+        self.rank_order.insert(new_rank);
+        self.pending_removals.remove(&new_rank.into());
+
+        Ok(new_rank)
+    }
+
+    /// Sets the perks note shown alongside a rank, or clears it if `perks` is None.
+    /// Returns false (and does nothing) if the role isn't a rank.
+    pub fn set_perks(&mut self, role_id: serenity::RoleId, perks: Option<String>) -> bool
+    {
+        if !self.contains_role(role_id)
+        {
+            return false;
+        }
+
+        match perks
+        {
+            Some(perks) => self.perks.insert(role_id, perks),
+            None => self.perks.remove(&role_id),
+        };
+        true
+    }
+
+    /// Gets the perks note for a rank, if it has one.
+    pub fn perks(&self, role_id: serenity::RoleId) -> Option<&str>
+    {
+        self.perks.get(&role_id).map(String::as_str)
+    }
+
+    /// Returns true if `role_id` is the role for one of the ranks in this list.
+    pub fn contains_role(&self, role_id: serenity::RoleId) -> bool
+    {
+        // RankHash only looks at the ids, so the word count here doesn't matter.
+        self.guild_id.is_some_and(|guild_id| self.rank_set.contains(&Rank::new(guild_id, role_id, 0).into()))
+    }
+
+    /// Multiplies every rank's minimum_word_count by `factor`, rounding to the nearest word.
+    /// Roles keep their ranks, only the thresholds change.
+    ///
+    /// # Errors
+    ///
+    /// [AdjustThresholdsError::InvalidFactor] - `factor` isn't a finite number above 0.
+    /// [AdjustThresholdsError::Collision] - Two ranks would end up with the same threshold.
+    /// [AdjustThresholdsError::TooLarge] - A threshold would be too big to store.
+    ///
+    /// The list is left unchanged if there's an error.
+    pub fn scale(&mut self, factor: f32) -> Result<(), AdjustThresholdsError>
+    {
+        if !factor.is_finite() || factor <= 0.0
+        {
+            return Err(AdjustThresholdsError::InvalidFactor);
+        }
+
+        // Float to int casts saturate, so this clamps to u32::MAX.
+        self.remap_thresholds(|count| (count as f64 * factor as f64).round() as u32)
+    }
+
+    /// Adds `delta` to every rank's minimum_word_count, stopping at 0 for negative shifts.
+    /// Roles keep their ranks, only the thresholds change.
+    ///
+    /// # Errors
+    ///
+    /// [AdjustThresholdsError::Collision] - Two ranks would end up with the same threshold,
+    /// usually because more than one of them got clamped to 0.
+    /// [AdjustThresholdsError::TooLarge] - A threshold would be too big to store.
+    ///
+    /// The list is left unchanged if there's an error.
+    pub fn shift(&mut self, delta: i32) -> Result<(), AdjustThresholdsError>
+    {
+        self.remap_thresholds(|count| count.saturating_add_signed(delta))
+    }
+
+    /// Replaces every rank's minimum_word_count with `remap(minimum_word_count)`.
+    /// Checks the new thresholds before changing anything, so the list is untouched on error.
+    fn remap_thresholds(&mut self, remap: impl Fn(u32) -> u32) -> Result<(), AdjustThresholdsError>
+    {
+        let mut rank_order = BTreeSet::new();
+        for rank in self.rank_order.iter()
+        {
+            let minimum_word_count = remap(rank.minimum_word_count);
+            if minimum_word_count > i32::MAX as u32
+            {
+                return Err(AdjustThresholdsError::TooLarge(minimum_word_count));
+            }
+
+            let new_rank = Rank {
+                rank_id: rank.rank_id,
+                minimum_word_count,
+            };
+            if !rank_order.insert(new_rank)
+            {
+                return Err(AdjustThresholdsError::Collision(minimum_word_count));
+            }
         }
+
+        self.rank_set = rank_order.iter().map(|rank| (*rank).into()).collect();
+        self.rank_order = rank_order;
+        Ok(())
+    }
+
+    /// Gets the ranks that have been removed but not saved yet.
+    /// These are deleted from the database the next time the list is saved.
+    pub fn pending_removals(&self) -> impl Iterator<Item = Rank> + '_
+    {
+        self.pending_removals.iter().map(|rank| rank.0)
     }
 
     /// Gets the highest rank that has a lower minimum_word_count than the provided word_count.
-    pub fn get_rank_for_word_count(&self, word_count: u32) -> Rank
+    /// If word_count is below every rank, this is the lowest rank.
+    ///
+    /// Returns None if there aren't any ranks.
+    pub fn get_rank_for_word_count(&self, word_count: u32) -> Option<Rank>
     {
-        let mut highest_rank = self.rank_order.first().expect("Expected there to be at least one rank!");
+        let mut highest_rank = self.rank_order.first()?;
         for rank in self.rank_order.iter()
         {
             // We can stop iterating as soon as we find a rank that is higher than our word count,
@@ -293,7 +652,161 @@ impl RankList
             highest_rank = rank;
         }
 
-        return *highest_rank;
+        Some(*highest_rank)
+    }
+
+    /// Gets the lowest rank with a minimum_word_count above `word_count`,
+    /// which is the next rank someone with `word_count` words can reach.
+    ///
+    /// Returns None if they're already at (or past) the top rank.
+    pub fn get_next_rank(&self, word_count: u32) -> Option<Rank>
+    {
+        self.rank_order.iter()
+            .find(|rank| rank.minimum_word_count > word_count)
+            .copied()
+    }
+
+    /// Gets how many more words someone with `word_count` words needs to reach [RankList::get_next_rank].
+    ///
+    /// Returns None if they're already at (or past) the top rank.
+    pub fn words_until_next_rank(&self, word_count: u32) -> Option<u32>
+    {
+        // The next rank is always strictly above word_count, so this can't underflow.
+        self.get_next_rank(word_count)
+            .map(|next_rank| next_rank.minimum_word_count - word_count)
+    }
+
+    /// Works out which rank roles to add and remove for a member with `member_roles`
+    /// who has written `word_count` words.
+    ///
+    /// Members can't be demoted, so nothing changes unless `word_count` reaches a rank above the
+    /// highest rank role they already have. When it does, that rank's role is added and their
+    /// lower rank roles are removed. Roles that aren't ranks in this list are never touched.
+    pub fn rank_role_changes(&self, member_roles: &[RoleId], word_count: u32) -> RankRoleChanges
+    {
+        let Some(target) = self.get_rank_for_word_count(word_count)
+            .filter(|rank| rank.minimum_word_count <= word_count) else
+        {
+            return RankRoleChanges::default();
+        };
+
+        let current_highest = self.rank_order.iter()
+            .rev()
+            .find(|rank| member_roles.contains(&rank.rank_id.role_id));
+        if current_highest.is_some_and(|rank| rank.minimum_word_count >= target.minimum_word_count)
+        {
+            return RankRoleChanges::default();
+        }
+
+        RankRoleChanges {
+            add: Some(target.rank_id.role_id),
+            remove: self.rank_order.iter()
+                .take_while(|rank| rank.minimum_word_count < target.minimum_word_count)
+                .map(|rank| rank.rank_id.role_id)
+                .filter(|role_id| member_roles.contains(role_id))
+                .collect(),
+        }
+    }
+
+    /// Gets every rank someone would reach going from `from` words to `to` words,
+    /// ordered from lowest to highest.
+    /// A rank counts as reached if its minimum_word_count is above `from` and at most `to`,
+    /// so the rank you already have at `from` isn't included.
+    pub fn ranks_crossed(&self, from: u32, to: u32) -> Vec<Rank>
+    {
+        self.rank_order.iter()
+            .skip_while(|rank| rank.minimum_word_count <= from)
+            .take_while(|rank| rank.minimum_word_count <= to)
+            .copied()
+            .collect()
+    }
+
+    /// Gets how far `word_count` is through the band between the current rank and the next one,
+    /// from 0.0 (just reached the current rank) up to but not including 1.0.
+    /// Below the lowest rank, the band starts at 0 words.
+    ///
+    /// Returns None if there's no rank above `word_count`.
+    pub fn band_progress(&self, word_count: u32) -> Option<f32>
+    {
+        let next_threshold = self.get_next_rank(word_count)?.minimum_word_count;
+        let current_threshold = self.rank_order.iter()
+            .rev()
+            .find(|rank| rank.minimum_word_count <= word_count)
+            .map_or(0, |rank| rank.minimum_word_count);
+
+        Some((word_count - current_threshold) as f32 / (next_threshold - current_threshold) as f32)
+    }
+
+    /// Returns true if there's a rank that starts at 0 words.
+    pub fn has_zero_rank(&self) -> bool
+    {
+        self.rank_order.first().is_some_and(|rank| rank.minimum_word_count == 0)
+    }
+
+    /// Checks that there's a rank at 0 words, so everyone has a rank from the start.
+    /// Lists without one are still allowed, this is just so commands can warn admins about it.
+    ///
+    /// # Errors
+    ///
+    /// [MissingBaseRankError::NoRanks] - There aren't any ranks at all.
+    /// [MissingBaseRankError::LowestRankAbove] - The lowest rank needs more than 0 words.
+    pub fn validate_has_base_rank(&self) -> Result<(), MissingBaseRankError>
+    {
+        match self.rank_order.first()
+        {
+            None => Err(MissingBaseRankError::NoRanks),
+            Some(rank) if rank.minimum_word_count > 0 => Err(MissingBaseRankError::LowestRankAbove(*rank)),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Finds pairs of neighbouring ranks where the higher rank needs more than [GAP_FACTOR] times
+    /// the words of the lower one, e.g. 1,000 followed by 50,000.
+    /// A rank at 0 words is never the lower half of a gap, since any threshold is infinitely far from 0;
+    /// a missing first rank is reported by [RankList::validate_has_base_rank] instead.
+    pub fn find_gaps(&self) -> Vec<(Rank, Rank)>
+    {
+        self.rank_order.iter()
+            .zip(self.rank_order.iter().skip(1))
+            .filter(|(lower, higher)| lower.minimum_word_count > 0
+                && higher.minimum_word_count as u64 > lower.minimum_word_count as u64 * GAP_FACTOR as u64)
+            .map(|(lower, higher)| (*lower, *higher))
+            .collect()
+    }
+
+    /// Checks the whole ladder for problems and returns every one it finds.
+    /// An empty list means the ladder is fine.
+    ///
+    /// `get_role_object` is used to look up each rank's role, the same way as [Rank::to_rank].
+    /// `bot_highest_position` is the position of the bot's highest role, which decides
+    /// which roles the bot is allowed to assign.
+    pub fn validate<G: GuildLike<R>, R: RoleLike>(&self, get_role_object: &G, bot_highest_position: u16) -> Vec<LadderProblem>
+    {
+        let mut problems = Vec::new();
+
+        if !self.has_zero_rank()
+        {
+            problems.push(LadderProblem::NoZeroRank);
+        }
+
+        for rank in self.rank_order.iter()
+        {
+            if rank.minimum_word_count > i32::MAX as u32
+            {
+                problems.push(LadderProblem::ThresholdTooLarge(*rank));
+            }
+
+            match get_role_object.role(rank.rank_id.role_id)
+            {
+                None => problems.push(LadderProblem::DeletedRole(*rank)),
+                Some(role) if !can_bot_manage_role(bot_highest_position, role.position()) => problems.push(LadderProblem::CannotManageRole(*rank)),
+                Some(_) => (),
+            }
+        }
+
+        problems.extend(self.find_gaps().into_iter().map(|(lower, higher)| LadderProblem::Gap(lower, higher)));
+
+        problems
     }
 
     /// Loads a RankList from a database.
@@ -305,40 +818,127 @@ impl RankList
             .fetch_all(db)
             .await?;
 
-        let ranks: Vec<Rank> = ranks.iter().map(|rank| rank.into()).collect();
+        Self::from_records(&ranks)
+    }
+
+    /// Builds a RankList out of database records, including their perks.
+    fn from_records(records: &[DbRankId]) -> anyhow::Result<Self>
+    {
+        let ranks: Vec<Rank> = records.iter().map(|rank| rank.into()).collect();
         // Convert our vec of ranks into a RankList
-        ranks.as_slice().try_into()
+        let mut rank_list: Self = ranks.as_slice().try_into()?;
+
+        for record in records
+        {
+            if let Some(perks) = &record.perks
+            {
+                rank_list.perks.insert(serenity::RoleId::new(record.role_id as u64), perks.clone());
+            }
+        }
+
+        Ok(rank_list)
+    }
+
+    /// Turns the live ranks back into database records.
+    /// Pending removals aren't included.
+    fn to_records(&self) -> Vec<DbRankId>
+    {
+        self.rank_order.iter()
+            .map(|rank| DbRankId {
+                guild_id: rank.rank_id.guild_id.into(),
+                role_id: rank.rank_id.role_id.into(),
+                minimum_word_count: rank.minimum_word_count as i32,
+                perks: self.perks.get(&rank.rank_id.role_id).cloned(),
+            })
+            .collect()
+    }
+
+    /// Loads the RankList of every guild in the database at once.
+    /// This only makes one query, so it's meant for maintenance tasks
+    /// that need to go over every guild.
+    pub async fn load_all_guilds(db: &PgPool) -> anyhow::Result<HashMap<serenity::GuildId, Self>>
+    {
+        let ranks: Vec<DbRankId> = sqlx::query_as!(DbRankId, "SELECT * FROM rank_table;")
+            .fetch_all(db)
+            .await?;
+
+        Self::group_by_guild(&ranks)
+    }
+
+    /// Splits database records from any number of guilds into one RankList per guild.
+    fn group_by_guild(records: &[DbRankId]) -> anyhow::Result<HashMap<serenity::GuildId, Self>>
+    {
+        let mut grouped: HashMap<serenity::GuildId, Vec<DbRankId>> = HashMap::new();
+        for record in records
+        {
+            grouped.entry(serenity::GuildId::new(record.guild_id as u64)).or_default().push(record.clone());
+        }
+
+        grouped.into_iter()
+            .map(|(guild_id, records)| Ok((guild_id, Self::from_records(&records)?)))
+            .collect()
     }
 
     /// Consumes this [RankList] and saves it to the database
+    ///
+    /// Everything is saved in one transaction. If any statement fails, the `?` drops the
+    /// transaction before it's committed, which rolls it back, so the table is never left half saved.
+    /// The unique word count constraint is only checked when it commits,
+    /// so thresholds can be moved past each other in a single save.
     pub async fn save(self, db: &PgPool) -> anyhow::Result<()>
     {
-        for rank in self.rank_order.iter()
-        {
-            let guild_id: i64 = rank.rank_id.guild_id.into();
-            let role_id: i64 = rank.rank_id.role_id.into();
-            let minimum_word_count: i32 = rank.minimum_word_count as i32;
-            println!("{}", minimum_word_count);
-            sqlx::query!("INSERT INTO rank_table (guild_id, role_id, minimum_word_count) VALUES ($1, $2, $3) ON CONFLICT (guild_id, role_id) DO UPDATE SET minimum_word_count = excluded.minimum_word_count;", guild_id, role_id, minimum_word_count)
-                // Okay we don't actually need PgPool to be mutable. I... guess that makes sense?
-                // Idk.
-                .execute(db)
-                .await?;
-            } 
-
-        // Take the list of pending removals and clear out the cache 
-        for rank in self.pending_removals.iter()
-        {
-            let guild_id: i64 = rank.0.rank_id.guild_id.into();
-            let role_id: i64 = rank.0.rank_id.role_id.into();
-            sqlx::query!("DELETE FROM rank_table WHERE guild_id = $1 AND role_id = $2;", guild_id, role_id)
-                .execute(db)
-                .await?;
-            }
+        let mut transaction = db.begin().await?;
+
+        // Take the list of pending removals and clear out the cache.
+        // This happens before the inserts so a relinked rank doesn't hit the
+        // unique word count constraint while its old row is still around.
+        let (removed_guild_ids, removed_role_ids): (Vec<i64>, Vec<i64>) = self.pending_removals.iter()
+            .map(|rank| (i64::from(rank.0.rank_id.guild_id), i64::from(rank.0.rank_id.role_id)))
+            .unzip();
+        sqlx::query!("DELETE FROM rank_table WHERE (guild_id, role_id) IN (SELECT * FROM UNNEST($1::bigint[], $2::bigint[]));", &removed_guild_ids, &removed_role_ids)
+            .execute(&mut *transaction)
+            .await?;
+
+        // Upsert every rank in one statement. UNNEST zips the arrays back up into rows.
+        let records = self.to_records();
+        let guild_ids: Vec<i64> = records.iter().map(|rank| rank.guild_id).collect();
+        let role_ids: Vec<i64> = records.iter().map(|rank| rank.role_id).collect();
+        let minimum_word_counts: Vec<i32> = records.iter().map(|rank| rank.minimum_word_count).collect();
+        let perks: Vec<Option<String>> = records.into_iter().map(|rank| rank.perks).collect();
+        sqlx::query!("INSERT INTO rank_table (guild_id, role_id, minimum_word_count, perks) SELECT * FROM UNNEST($1::bigint[], $2::bigint[], $3::integer[], $4::text[]) ON CONFLICT (guild_id, role_id) DO UPDATE SET minimum_word_count = excluded.minimum_word_count, perks = excluded.perks;", &guild_ids, &role_ids, &minimum_word_counts, &perks as &[Option<String>])
+            .execute(&mut *transaction)
+            .await?;
 
+        transaction.commit().await?;
         Ok(())
     }
 
+    /// Writes every rank out as a JSON array of [ExportedRank]s, lowest rank first.
+    pub fn to_json(&self) -> serde_json::Result<String>
+    {
+        let ranks: Vec<ExportedRank> = self.rank_order.iter()
+            .map(|rank| ExportedRank {
+                guild_id: rank.rank_id.guild_id,
+                role_id: rank.rank_id.role_id,
+                minimum_word_count: rank.minimum_word_count,
+                perks: self.perks.get(&rank.rank_id.role_id).cloned(),
+            })
+            .collect();
+        serde_json::to_string_pretty(&ranks)
+    }
+
+    /// Gets the number of ranks in the list. Ranks waiting to be removed aren't counted.
+    pub fn len(&self) -> usize
+    {
+        self.rank_order.len()
+    }
+
+    /// Returns true if the list doesn't have any ranks.
+    pub fn is_empty(&self) -> bool
+    {
+        self.rank_order.is_empty()
+    }
+
     pub fn iter(&self) -> std::collections::btree_set::Iter<'_, Rank>
     {
         self.rank_order.iter()
@@ -358,6 +958,7 @@ impl From<Rank> for RankList
             rank_order,
             rank_set,
             pending_removals: HashSet::new(),
+            perks: HashMap::new(),
         }
     }
 }
@@ -389,6 +990,7 @@ impl TryFrom<&[Rank]> for RankList
                 rank_set: HashSet::new(),
                 rank_order: BTreeSet::new(),
                 pending_removals: HashSet::new(),
+                perks: HashMap::new(),
             })
         }
     }
@@ -403,6 +1005,7 @@ mod tests
     #[derive(Debug, PartialEq, Eq)]
     struct MockRole {
         role_id: serenity::RoleId,
+        position: u16,
     }
 
     impl RoleLike for MockRole
@@ -417,13 +1020,54 @@ mod tests
             const GUILD_ID: u64 = 1;
             GUILD_ID.into()
         }
+
+        fn position(&self) -> u16
+        {
+            self.position
+        }
     }
 
     type MockGuild = HashMap<serenity::RoleId, MockRole>;
 
+    struct MockMember {
+        user_id: serenity::UserId,
+        roles: Vec<serenity::RoleId>,
+        // Roles above the bot, which Discord won't let it add or remove.
+        locked_roles: Vec<serenity::RoleId>,
+    }
+
+    impl MemberLike for MockMember
+    {
+        fn user_id(&self) -> serenity::UserId {
+            self.user_id
+        }
+
+        fn roles(&self) -> &[serenity::RoleId] {
+            &self.roles
+        }
+
+        async fn add_role(&mut self, _http: &serenity::Http, role_id: serenity::RoleId) -> serenity::Result<()> {
+            if self.locked_roles.contains(&role_id)
+            {
+                return Err(serenity::Error::Other("Missing permissions"));
+            }
+            self.roles.push(role_id);
+            Ok(())
+        }
+
+        async fn remove_role(&mut self, _http: &serenity::Http, role_id: serenity::RoleId) -> serenity::Result<()> {
+            if self.locked_roles.contains(&role_id)
+            {
+                return Err(serenity::Error::Other("Missing permissions"));
+            }
+            self.roles.retain(|id| *id != role_id);
+            Ok(())
+        }
+    }
+
     impl GuildLike<MockRole> for MockGuild
     {
-        fn role<'a>(&'a self, role_id: serenity::RoleId) -> Option<&'a MockRole> {
+        fn role(&self, role_id: serenity::RoleId) -> Option<&MockRole> {
             self.get(&role_id) 
         }
 
@@ -434,11 +1078,22 @@ mod tests
         }
     }
 
+    fn record(guild_id: i64, role_id: i64, minimum_word_count: i32, perks: Option<&str>) -> DbRankId
+    {
+        DbRankId {
+            guild_id,
+            role_id,
+            minimum_word_count,
+            perks: perks.map(str::to_string),
+        }
+    }
+
     fn create_role_in_guild(mock_guild: &mut MockGuild, role_id: serenity::RoleId)
     {
         let mock_role = MockRole
         {
             role_id,
+            position: 1,
         };
 
         mock_guild.insert(role_id, mock_role);
@@ -465,6 +1120,17 @@ mod tests
         assert_eq!(rank.role, mock_guild.get(&ROLE_ID.into()).unwrap());
     }
 
+    #[test]
+    pub fn discord_rank_role_getter()
+    {
+        const ROLE_ID: u64 = 1;
+        let mut mock_guild = MockGuild::new();
+        create_role_in_guild(&mut mock_guild, ROLE_ID.into());
+
+        let rank = Rank::new(1.into(), ROLE_ID.into(), 0).to_rank(&mock_guild).expect("Expected to get rank");
+        assert_eq!(rank.role().id(), serenity::RoleId::new(ROLE_ID));
+    }
+
     #[test]
     pub fn rank_to_rank_id()
     {
@@ -473,6 +1139,7 @@ mod tests
 
         let mock_role = MockRole {
             role_id: ROLE_ID.into(),
+            position: 1,
         };
 
         let mock_rank = DiscordRank
@@ -502,7 +1169,7 @@ mod tests
 
         let rank_list: RankList = first_rank.into();
 
-        assert_eq!(rank_list.get_rank_for_word_count(0), first_rank);
+        assert_eq!(rank_list.get_rank_for_word_count(0), Some(first_rank));
     }
 
     #[test]
@@ -525,8 +1192,67 @@ mod tests
         };
         let rank_list: RankList = vec![first_rank, second_rank].as_slice().try_into().unwrap();
 
-        assert_eq!(rank_list.get_rank_for_word_count(10), first_rank);
-        assert_eq!(rank_list.get_rank_for_word_count(100), second_rank);
+        assert_eq!(rank_list.get_rank_for_word_count(10), Some(first_rank));
+        assert_eq!(rank_list.get_rank_for_word_count(100), Some(second_rank));
+    }
+
+    #[test]
+    pub fn get_next_rank_below_and_at_top()
+    {
+        let first_rank = Rank::new(1.into(), 1.into(), 0);
+        let second_rank = Rank::new(1.into(), 2.into(), 100);
+        let rank_list: RankList = vec![first_rank, second_rank].as_slice().try_into().unwrap();
+
+        assert_eq!(rank_list.get_next_rank(0).map(|rank| rank.rank_id), Some(second_rank.rank_id));
+        assert_eq!(rank_list.get_next_rank(99).map(|rank| rank.rank_id), Some(second_rank.rank_id));
+        assert_eq!(rank_list.get_next_rank(100), None);
+        assert_eq!(rank_list.get_next_rank(5000), None);
+    }
+
+    #[test]
+    pub fn words_until_next_rank_at_and_below_threshold()
+    {
+        let rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+            Rank::new(1.into(), 3.into(), 450),
+        ].as_slice().try_into().unwrap();
+
+        assert_eq!(rank_list.words_until_next_rank(0), Some(100));
+        assert_eq!(rank_list.words_until_next_rank(99), Some(1));
+        assert_eq!(rank_list.words_until_next_rank(100), Some(350));
+        assert_eq!(rank_list.words_until_next_rank(450), None);
+    }
+
+    #[test]
+    pub fn validate_has_base_rank_with_zero_rank()
+    {
+        let rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+        ].as_slice().try_into().unwrap();
+        assert_eq!(rank_list.validate_has_base_rank(), Ok(()));
+    }
+
+    #[test]
+    pub fn validate_has_base_rank_without_zero_rank()
+    {
+        let lowest = Rank::new(1.into(), 1.into(), 50);
+        let rank_list: RankList = vec![lowest, Rank::new(1.into(), 2.into(), 100)].as_slice().try_into().unwrap();
+        let err = rank_list.validate_has_base_rank().unwrap_err();
+        assert_eq!(err, MissingBaseRankError::LowestRankAbove(lowest));
+        assert!(err.to_string().contains("50 words"));
+
+        let rank_list: RankList = Vec::<Rank>::new().as_slice().try_into().unwrap();
+        assert_eq!(rank_list.validate_has_base_rank(), Err(MissingBaseRankError::NoRanks));
+    }
+
+    #[test]
+    pub fn get_rank_for_word_count_empty_list_is_none()
+    {
+        let rank_list: RankList = Vec::<Rank>::new().as_slice().try_into().unwrap();
+        assert_eq!(rank_list.get_rank_for_word_count(0), None);
+        assert_eq!(rank_list.get_rank_for_word_count(1000), None);
     }
 
     #[test]
@@ -548,7 +1274,7 @@ mod tests
             minimum_word_count: 0,
         };
         let vec = vec![first_rank, second_rank];
-        let rank_list: Result<RankList, anyhow::Error> = vec.as_slice().try_into();
+        let _rank_list: Result<RankList, anyhow::Error> = vec.as_slice().try_into();
     }
 
     #[test]
@@ -563,10 +1289,415 @@ mod tests
         };
         let mut rank_list: RankList = first_rank.into();
         assert!(rank_list.pending_removals.is_empty());
+        assert!(rank_list.remove_rank(first_rank));
+        assert_eq!(rank_list.pending_removals.len(), 1);
+    }
+
+    #[test]
+    pub fn len_excludes_removed_ranks()
+    {
+        let first_rank = Rank::new(1.into(), 1.into(), 0);
+        let mut rank_list: RankList = first_rank.into();
+        rank_list.add_rank(Rank::new(1.into(), 2.into(), 100)).unwrap();
+        assert_eq!(rank_list.len(), 2);
+
         rank_list.remove_rank(first_rank);
+        assert_eq!(rank_list.len(), 1);
+        assert!(!rank_list.is_empty());
+
+        rank_list.remove_rank(Rank::new(1.into(), 2.into(), 0));
+        assert!(rank_list.is_empty());
+    }
+
+    #[test]
+    pub fn contains_role_present_and_missing()
+    {
+        let mut rank_list: RankList = Rank::new(1.into(), 1.into(), 0).into();
+        rank_list.add_rank(Rank::new(1.into(), 2.into(), 100)).unwrap();
+
+        assert!(rank_list.contains_role(1.into()));
+        assert!(rank_list.contains_role(2.into()));
+        assert!(!rank_list.contains_role(3.into()));
+
+        rank_list.remove_rank(Rank::new(1.into(), 2.into(), 0));
+        assert!(!rank_list.contains_role(2.into()));
+    }
+
+    #[test]
+    pub fn clear_queues_every_rank_for_removal()
+    {
+        let mut rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+        ].as_slice().try_into().unwrap();
+        rank_list.set_perks(2.into(), Some("Access to #novelists".to_string()));
+
+        rank_list.clear();
+        assert_eq!(rank_list.pending_removals.len(), 2);
+        assert!(rank_list.rank_set.is_empty());
+        assert!(rank_list.rank_order.is_empty());
+        assert!(rank_list.to_records().is_empty());
+        assert_eq!(rank_list.perks(2.into()), None);
+    }
+
+    #[test]
+    pub fn to_json_round_trips()
+    {
+        let mut rank_list: RankList = vec![
+            Rank::new(1.into(), 2.into(), 100),
+            Rank::new(1.into(), 1.into(), 0),
+        ].as_slice().try_into().unwrap();
+        rank_list.set_perks(2.into(), Some("Unlocks #lounge".to_string()));
+
+        let json = rank_list.to_json().unwrap();
+        assert!(json.contains("\"role_id\": \"2\""));
+        let exported: Vec<ExportedRank> = serde_json::from_str(&json).unwrap();
+        assert_eq!(exported, vec![
+            ExportedRank { guild_id: 1.into(), role_id: 1.into(), minimum_word_count: 0, perks: None },
+            ExportedRank { guild_id: 1.into(), role_id: 2.into(), minimum_word_count: 100, perks: Some("Unlocks #lounge".to_string()) },
+        ]);
+    }
+
+    #[test]
+    pub fn parse_rank_import_builds_ranks_for_guild()
+    {
+        let mut mock_guild = MockGuild::new();
+        create_role_in_guild(&mut mock_guild, 1.into());
+        create_role_in_guild(&mut mock_guild, 2.into());
+
+        // Hand-written files don't need a guild_id, and ids can be numbers or strings.
+        let json = r#"[{"role_id": 1, "minimum_word_count": 0}, {"role_id": "2", "minimum_word_count": 5000}]"#;
+        let ranks = parse_rank_import(json, &mock_guild).unwrap();
+        let ranks: Vec<(RankId, u32)> = ranks.iter().map(|(rank, _)| (rank.rank_id, rank.minimum_word_count)).collect();
+        assert_eq!(ranks, vec![
+            (Rank::new(1.into(), 1.into(), 0).rank_id, 0),
+            (Rank::new(1.into(), 2.into(), 0).rank_id, 5000),
+        ]);
+    }
+
+    #[test]
+    pub fn export_then_import_into_another_guild()
+    {
+        let mut exported: RankList = vec![
+            Rank::new(9.into(), 1.into(), 0),
+            Rank::new(9.into(), 2.into(), 100),
+        ].as_slice().try_into().unwrap();
+        exported.set_perks(2.into(), Some("Unlocks #lounge".to_string()));
+
+        let mut mock_guild = MockGuild::new();
+        create_role_in_guild(&mut mock_guild, 1.into());
+        create_role_in_guild(&mut mock_guild, 2.into());
+
+        let ranks = parse_rank_import(&exported.to_json().unwrap(), &mock_guild).unwrap();
+        assert_eq!(ranks[1].1.as_deref(), Some("Unlocks #lounge"));
+        let mut imported: RankList = Vec::<Rank>::new().as_slice().try_into().unwrap();
+        imported.add_ranks(&ranks.iter().map(|(rank, _)| *rank).collect::<Vec<_>>()).unwrap();
+        assert_eq!(imported.to_records().iter().map(|record| (record.guild_id, record.role_id, record.minimum_word_count)).collect::<Vec<_>>(),
+            vec![(1, 1, 0), (1, 2, 100)]);
+    }
+
+    #[test]
+    pub fn parse_rank_import_rejects_bad_files()
+    {
+        let mut mock_guild = MockGuild::new();
+        create_role_in_guild(&mut mock_guild, 1.into());
+
+        let result = parse_rank_import(r#"[{"role_id": 3, "minimum_word_count": 0}]"#, &mock_guild);
+        assert!(matches!(result, Err(ImportRanksError::UnknownRole(role_id)) if role_id == RoleId::new(3)));
+
+        let result = parse_rank_import(r#"[{"role_id": 1, "minimum_word_count": 3000000000}]"#, &mock_guild);
+        assert!(matches!(result, Err(ImportRanksError::TooLarge(3_000_000_000))));
+
+        let result = parse_rank_import(r#"{"role_id": 1}"#, &mock_guild);
+        assert!(matches!(result, Err(ImportRanksError::InvalidJson(_))));
+    }
+
+    #[test]
+    pub fn import_with_duplicate_word_counts_fails()
+    {
+        let mut mock_guild = MockGuild::new();
+        create_role_in_guild(&mut mock_guild, 1.into());
+        create_role_in_guild(&mut mock_guild, 2.into());
+
+        let ranks = parse_rank_import(r#"[{"role_id": 1, "minimum_word_count": 0}, {"role_id": 2, "minimum_word_count": 0}]"#, &mock_guild).unwrap();
+        let ranks: Vec<Rank> = ranks.into_iter().map(|(rank, _)| rank).collect();
+        let mut rank_list: RankList = Vec::<Rank>::new().as_slice().try_into().unwrap();
+        assert!(matches!(rank_list.add_ranks(&ranks), Err(AddRankError::RankExistsWithWordCount(_))));
+    }
+
+    #[test]
+    pub fn add_ranks_stops_at_first_error()
+    {
+        let mut rank_list: RankList = Vec::<Rank>::new().as_slice().try_into().unwrap();
+        let ranks = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 0),
+            Rank::new(1.into(), 3.into(), 100),
+        ];
+        assert!(matches!(rank_list.add_ranks(&ranks), Err(AddRankError::RankExistsWithWordCount(_))));
+        assert_eq!(rank_list.len(), 1);
+    }
+
+    #[test]
+    pub fn to_json_empty_list()
+    {
+        let rank_list: RankList = Vec::<Rank>::new().as_slice().try_into().unwrap();
+        assert_eq!(rank_list.to_json().unwrap(), "[]");
+    }
+
+    #[test]
+    pub fn remove_missing_role_returns_false()
+    {
+        let rank = Rank::new(1.into(), 1.into(), 0);
+        let mut rank_list: RankList = rank.into();
+        assert!(!rank_list.remove_rank(Rank::new(1.into(), 2.into(), 0)));
+        assert!(rank_list.pending_removals.is_empty());
+
+        // Removing the same rank twice only queues it once.
+        assert!(rank_list.remove_rank(rank));
+        assert!(!rank_list.remove_rank(rank));
         assert_eq!(rank_list.pending_removals.len(), 1);
     }
 
+    #[test]
+    pub fn pending_removals_reflects_removed_rank()
+    {
+        let rank = Rank::new(1.into(), 1.into(), 0);
+        let mut rank_list: RankList = rank.into();
+        assert_eq!(rank_list.pending_removals().count(), 0);
+
+        rank_list.remove_rank(rank);
+        let pending: Vec<Rank> = rank_list.pending_removals().collect();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].rank_id, rank.rank_id);
+    }
+
+    #[test]
+    pub fn relink_keeps_threshold_and_updates_role()
+    {
+        let zero = Rank::new(1.into(), 1.into(), 0);
+        let old = Rank::new(1.into(), 2.into(), 500);
+        let mut rank_list: RankList = vec![zero, old].as_slice().try_into().unwrap();
+
+        let new = rank_list.relink(2.into(), 3.into()).unwrap();
+        assert_eq!(new.rank_id.role_id, RoleId::new(3));
+        assert_eq!(new.minimum_word_count, 500);
+        assert_eq!(rank_list.get_rank_for_word_count(500).unwrap().rank_id.role_id, RoleId::new(3));
+        assert_eq!(rank_list.rank_set.len(), 2);
+        assert_eq!(rank_list.rank_order.len(), 2);
+
+        let pending: Vec<Rank> = rank_list.pending_removals().collect();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].rank_id.role_id, RoleId::new(2));
+    }
+
+    #[test]
+    pub fn relink_fails_for_missing_or_existing_roles()
+    {
+        let zero = Rank::new(1.into(), 1.into(), 0);
+        let hundred = Rank::new(1.into(), 2.into(), 100);
+        let mut rank_list: RankList = vec![zero, hundred].as_slice().try_into().unwrap();
+
+        assert!(matches!(rank_list.relink(5.into(), 6.into()), Err(RelinkRankError::NotARank(_))));
+        assert!(matches!(rank_list.relink(1.into(), 2.into()), Err(RelinkRankError::AlreadyARank(_))));
+        assert_eq!(rank_list.rank_order.len(), 2);
+        assert_eq!(rank_list.pending_removals().count(), 0);
+    }
+
+    #[test]
+    pub fn scale_preserves_order_and_roles()
+    {
+        let mut rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+            Rank::new(1.into(), 3.into(), 250),
+        ].as_slice().try_into().unwrap();
+
+        rank_list.scale(0.5).unwrap();
+        let ranks: Vec<(RoleId, u32)> = rank_list.iter().map(|rank| (rank.rank_id.role_id, rank.minimum_word_count)).collect();
+        assert_eq!(ranks, vec![(1.into(), 0), (2.into(), 50), (3.into(), 125)]);
+
+        rank_list.scale(4.0).unwrap();
+        let ranks: Vec<(RoleId, u32)> = rank_list.iter().map(|rank| (rank.rank_id.role_id, rank.minimum_word_count)).collect();
+        assert_eq!(ranks, vec![(1.into(), 0), (2.into(), 200), (3.into(), 500)]);
+        assert_eq!(rank_list.rank_set.len(), 3);
+        assert_eq!(rank_list.rank_set.get(&Rank::new(1.into(), 3.into(), 0).into()).unwrap().0.minimum_word_count, 500);
+    }
+
+    #[test]
+    pub fn scale_rejects_invalid_factors()
+    {
+        let mut rank_list: RankList = Rank::new(1.into(), 1.into(), 100).into();
+        assert_eq!(rank_list.scale(0.0), Err(AdjustThresholdsError::InvalidFactor));
+        assert_eq!(rank_list.scale(-2.0), Err(AdjustThresholdsError::InvalidFactor));
+        assert_eq!(rank_list.scale(f32::NAN), Err(AdjustThresholdsError::InvalidFactor));
+        assert_eq!(rank_list.iter().next().unwrap().minimum_word_count, 100);
+    }
+
+    #[test]
+    pub fn scale_rejects_collisions_and_overflow_without_changing_list()
+    {
+        let mut rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 1),
+            Rank::new(1.into(), 2.into(), 2),
+        ].as_slice().try_into().unwrap();
+
+        assert_eq!(rank_list.scale(0.1), Err(AdjustThresholdsError::Collision(0)));
+        assert!(matches!(rank_list.scale(2_000_000_000.0), Err(AdjustThresholdsError::TooLarge(_))));
+        let counts: Vec<u32> = rank_list.iter().map(|rank| rank.minimum_word_count).collect();
+        assert_eq!(counts, vec![1, 2]);
+    }
+
+    #[test]
+    pub fn shift_up_and_down()
+    {
+        let mut rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+        ].as_slice().try_into().unwrap();
+
+        rank_list.shift(500).unwrap();
+        let ranks: Vec<(RoleId, u32)> = rank_list.iter().map(|rank| (rank.rank_id.role_id, rank.minimum_word_count)).collect();
+        assert_eq!(ranks, vec![(1.into(), 500), (2.into(), 600)]);
+
+        rank_list.shift(-200).unwrap();
+        let ranks: Vec<(RoleId, u32)> = rank_list.iter().map(|rank| (rank.rank_id.role_id, rank.minimum_word_count)).collect();
+        assert_eq!(ranks, vec![(1.into(), 300), (2.into(), 400)]);
+    }
+
+    #[test]
+    pub fn shift_clamps_lowest_to_zero()
+    {
+        let mut rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 50),
+            Rank::new(1.into(), 2.into(), 200),
+        ].as_slice().try_into().unwrap();
+
+        rank_list.shift(-100).unwrap();
+        let counts: Vec<u32> = rank_list.iter().map(|rank| rank.minimum_word_count).collect();
+        assert_eq!(counts, vec![0, 100]);
+        assert!(rank_list.has_zero_rank());
+    }
+
+    #[test]
+    pub fn shift_rejects_collisions_and_overflow_without_changing_list()
+    {
+        let mut rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 50),
+            Rank::new(1.into(), 2.into(), 100),
+        ].as_slice().try_into().unwrap();
+
+        assert_eq!(rank_list.shift(-100), Err(AdjustThresholdsError::Collision(0)));
+        assert_eq!(rank_list.shift(i32::MAX), Err(AdjustThresholdsError::TooLarge(i32::MAX as u32 + 50)));
+        let counts: Vec<u32> = rank_list.iter().map(|rank| rank.minimum_word_count).collect();
+        assert_eq!(counts, vec![50, 100]);
+    }
+
+    #[test]
+    pub fn rank_role_changes_swaps_old_rank_for_new()
+    {
+        let rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+            Rank::new(1.into(), 3.into(), 1000),
+        ].as_slice().try_into().unwrap();
+
+        let changes = rank_list.rank_role_changes(&[1.into(), 50.into()], 150);
+        assert_eq!(changes, RankRoleChanges { add: Some(2.into()), remove: vec![1.into()] });
+    }
+
+    #[test]
+    pub fn rank_role_changes_nothing_to_do()
+    {
+        let rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+        ].as_slice().try_into().unwrap();
+
+        let changes = rank_list.rank_role_changes(&[2.into(), 50.into()], 500);
+        assert_eq!(changes, RankRoleChanges::default());
+    }
+
+    #[test]
+    pub fn rank_role_changes_never_demotes()
+    {
+        let rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+            Rank::new(1.into(), 3.into(), 1000),
+        ].as_slice().try_into().unwrap();
+
+        // Their count went down from 1000+ to 150, but they keep the rank they earned.
+        let changes = rank_list.rank_role_changes(&[3.into(), 50.into()], 150);
+        assert_eq!(changes, RankRoleChanges::default());
+    }
+
+    #[test]
+    pub fn rank_role_changes_below_lowest_rank()
+    {
+        let rank_list: RankList = vec![
+            Rank::new(1.into(), 2.into(), 100),
+            Rank::new(1.into(), 3.into(), 1000),
+        ].as_slice().try_into().unwrap();
+
+        let changes = rank_list.rank_role_changes(&[3.into(), 50.into()], 10);
+        assert_eq!(changes, RankRoleChanges::default());
+
+        let changes = RankList::from_records(&[]).unwrap().rank_role_changes(&[3.into()], 10);
+        assert_eq!(changes, RankRoleChanges::default());
+    }
+
+    #[tokio::test]
+    pub async fn apply_rank_role_changes_updates_member()
+    {
+        let rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+        ].as_slice().try_into().unwrap();
+        let mut member = MockMember { user_id: 7.into(), roles: vec![1.into(), 50.into()], locked_roles: vec![] };
+        let http = serenity::Http::new("");
+
+        let changes = rank_list.rank_role_changes(member.roles(), 150);
+        let failed = apply_rank_role_changes(&mut member, &http, &changes).await;
+        assert!(failed.is_empty());
+        assert_eq!(member.roles(), &[RoleId::new(50), RoleId::new(2)]);
+        assert_eq!(member.user_id(), serenity::UserId::new(7));
+    }
+
+    #[tokio::test]
+    pub async fn apply_rank_role_changes_keeps_going_after_failure()
+    {
+        let rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+        ].as_slice().try_into().unwrap();
+        let mut member = MockMember { user_id: 7.into(), roles: vec![1.into()], locked_roles: vec![1.into()] };
+        let http = serenity::Http::new("");
+
+        let changes = rank_list.rank_role_changes(member.roles(), 150);
+        let failed = apply_rank_role_changes(&mut member, &http, &changes).await;
+        let failed: Vec<RoleId> = failed.into_iter().map(|(role_id, _)| role_id).collect();
+        assert_eq!(failed, vec![RoleId::new(1)]);
+        assert_eq!(member.roles(), &[RoleId::new(1), RoleId::new(2)]);
+    }
+
+    #[tokio::test]
+    pub async fn apply_rank_role_changes_keeps_old_role_when_add_fails()
+    {
+        let rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+        ].as_slice().try_into().unwrap();
+        let mut member = MockMember { user_id: 7.into(), roles: vec![1.into()], locked_roles: vec![2.into()] };
+        let http = serenity::Http::new("");
+
+        let changes = rank_list.rank_role_changes(member.roles(), 150);
+        let failed = apply_rank_role_changes(&mut member, &http, &changes).await;
+        let failed: Vec<RoleId> = failed.into_iter().map(|(role_id, _)| role_id).collect();
+        assert_eq!(failed, vec![RoleId::new(2)]);
+        assert_eq!(member.roles(), &[RoleId::new(1)]);
+    }
+
     #[test]
     pub fn remove_role_then_add_back_removes_cached_rank()
     {
@@ -609,8 +1740,8 @@ mod tests
         assert!(result.is_err());
         assert_eq!(rank_list.rank_set.len(), 1);
         assert_eq!(rank_list.rank_order.len(), 1);
-        assert_eq!(rank_list.rank_set.iter().nth(0).unwrap().0.rank_id.role_id, RoleId::new(1));
-        assert_eq!(rank_list.rank_order.iter().nth(0).unwrap().rank_id.role_id, RoleId::new(1));
+        assert_eq!(rank_list.rank_set.iter().next().unwrap().0.rank_id.role_id, RoleId::new(1));
+        assert_eq!(rank_list.rank_order.iter().next().unwrap().rank_id.role_id, RoleId::new(1));
     }
 
     #[test]
@@ -636,8 +1767,223 @@ mod tests
         rank_list.add_rank(second_rank).unwrap();
         assert_eq!(rank_list.rank_set.len(), 1);
         assert_eq!(rank_list.rank_order.len(), 1);
-        assert_eq!(rank_list.rank_set.iter().nth(0).unwrap().0.minimum_word_count, 200);
-        assert_eq!(rank_list.rank_order.iter().nth(0).unwrap().minimum_word_count, 200);
+        assert_eq!(rank_list.rank_set.iter().next().unwrap().0.minimum_word_count, 200);
+        assert_eq!(rank_list.rank_order.iter().next().unwrap().minimum_word_count, 200);
+    }
+
+    #[test]
+    pub fn validate_reports_every_problem_with_broken_ladder()
+    {
+        let mut mock_guild = MockGuild::new();
+        create_role_in_guild(&mut mock_guild, 1.into());
+        mock_guild.insert(2.into(), MockRole { role_id: 2.into(), position: 10 });
+        // Role 3 is never created, as if it had been deleted.
+
+        let above_bot = Rank::new(1.into(), 2.into(), 500);
+        let deleted = Rank::new(1.into(), 3.into(), 1000);
+        let too_large = Rank::new(1.into(), 1.into(), u32::MAX);
+        let rank_list: RankList = vec![above_bot, deleted, too_large].as_slice().try_into().unwrap();
+
+        let problems = rank_list.validate(&mock_guild, 5);
+        assert_eq!(problems.len(), 5);
+        assert!(problems.contains(&LadderProblem::NoZeroRank));
+        assert!(problems.contains(&LadderProblem::CannotManageRole(above_bot)));
+        assert!(problems.contains(&LadderProblem::DeletedRole(deleted)));
+        assert!(problems.contains(&LadderProblem::ThresholdTooLarge(too_large)));
+        assert!(problems.contains(&LadderProblem::Gap(deleted, too_large)));
+    }
+
+    #[test]
+    pub fn find_gaps_only_reports_jumps_over_gap_factor()
+    {
+        let zero = Rank::new(1.into(), 1.into(), 0);
+        let hundred = Rank::new(1.into(), 2.into(), 100);
+        let thousand = Rank::new(1.into(), 3.into(), 1000);
+        let fifty_thousand = Rank::new(1.into(), 4.into(), 50000);
+        let rank_list: RankList = vec![zero, hundred, thousand, fifty_thousand].as_slice().try_into().unwrap();
+
+        // 0 -> 100 is ignored, and 100 -> 1000 is exactly GAP_FACTOR so it's fine.
+        assert_eq!(rank_list.find_gaps(), vec![(thousand, fifty_thousand)]);
+    }
+
+    #[test]
+    pub fn validate_healthy_ladder_has_no_problems()
+    {
+        let mut mock_guild = MockGuild::new();
+        create_role_in_guild(&mut mock_guild, 1.into());
+        create_role_in_guild(&mut mock_guild, 2.into());
+
+        let rank_list: RankList = vec![Rank::new(1.into(), 1.into(), 0), Rank::new(1.into(), 2.into(), 100)].as_slice().try_into().unwrap();
+        assert!(rank_list.validate(&mock_guild, 5).is_empty());
+    }
+
+    #[test]
+    pub fn ranks_crossed_from_zero_returns_ordered_ranks()
+    {
+        let zero = Rank::new(1.into(), 1.into(), 0);
+        let hundred = Rank::new(1.into(), 2.into(), 100);
+        let five_hundred = Rank::new(1.into(), 3.into(), 500);
+        let rank_list: RankList = vec![five_hundred, zero, hundred].as_slice().try_into().unwrap();
+
+        assert_eq!(rank_list.ranks_crossed(0, 499), vec![hundred]);
+        assert_eq!(rank_list.ranks_crossed(0, 500), vec![hundred, five_hundred]);
+        assert_eq!(rank_list.ranks_crossed(100, 10000), vec![five_hundred]);
+    }
+
+    #[test]
+    pub fn ranks_crossed_below_lowest_threshold_is_empty()
+    {
+        let rank_list: RankList = Rank::new(1.into(), 1.into(), 100).into();
+        assert!(rank_list.ranks_crossed(0, 99).is_empty());
+    }
+
+    #[test]
+    pub fn band_progress_at_edges_and_mid_band()
+    {
+        let rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+            Rank::new(1.into(), 3.into(), 500),
+        ].as_slice().try_into().unwrap();
+
+        assert_eq!(rank_list.band_progress(0), Some(0.0));
+        assert_eq!(rank_list.band_progress(60), Some(0.6));
+        assert_eq!(rank_list.band_progress(100), Some(0.0));
+        assert_eq!(rank_list.band_progress(300), Some(0.5));
+        assert_eq!(rank_list.band_progress(499), Some(399.0 / 400.0));
+    }
+
+    #[test]
+    pub fn band_progress_below_lowest_rank_starts_at_zero()
+    {
+        let rank_list: RankList = Rank::new(1.into(), 1.into(), 200).into();
+        assert_eq!(rank_list.band_progress(50), Some(0.25));
+    }
+
+    #[test]
+    pub fn band_progress_at_top_rank_is_none()
+    {
+        let rank_list: RankList = vec![
+            Rank::new(1.into(), 1.into(), 0),
+            Rank::new(1.into(), 2.into(), 100),
+        ].as_slice().try_into().unwrap();
+
+        assert_eq!(rank_list.band_progress(100), None);
+        assert_eq!(rank_list.band_progress(u32::MAX), None);
+    }
+
+    #[test]
+    pub fn group_by_guild_keeps_guilds_separate()
+    {
+        // Both guilds have a rank at 0, which would conflict if they were mixed together.
+        let ranks = vec![
+            record(1, 1, 0, None),
+            record(2, 2, 0, None),
+            record(1, 3, 100, None),
+            record(2, 4, 200, None),
+            record(2, 5, 300, None),
+        ];
+
+        let grouped = RankList::group_by_guild(&ranks).unwrap();
+        assert_eq!(grouped.len(), 2);
+
+        let first_guild = grouped.get(&1.into()).unwrap();
+        assert_eq!(first_guild.guild_id, Some(1.into()));
+        assert_eq!(first_guild.rank_order.len(), 2);
+        assert!(first_guild.iter().all(|rank| rank.rank_id.guild_id == GuildId::new(1)));
+
+        let second_guild = grouped.get(&2.into()).unwrap();
+        assert_eq!(second_guild.guild_id, Some(2.into()));
+        assert_eq!(second_guild.rank_order.len(), 3);
+        assert!(second_guild.iter().all(|rank| rank.rank_id.guild_id == GuildId::new(2)));
     }
+
+    #[test]
+    pub fn records_round_trip_preserves_perks()
+    {
+        let records = vec![
+            record(1, 1, 0, None),
+            record(1, 2, 500, Some("Unlocks #veterans")),
+        ];
+
+        let rank_list = RankList::from_records(&records).unwrap();
+        assert_eq!(rank_list.perks(2.into()), Some("Unlocks #veterans"));
+        assert_eq!(rank_list.perks(1.into()), None);
+
+        let saved = rank_list.to_records();
+        assert_eq!(saved.len(), 2);
+        let veteran = saved.iter().find(|rank| rank.role_id == 2).unwrap();
+        assert_eq!(veteran.minimum_word_count, 500);
+        assert_eq!(veteran.perks.as_deref(), Some("Unlocks #veterans"));
+        assert!(saved.iter().find(|rank| rank.role_id == 1).unwrap().perks.is_none());
+    }
+
+    #[test]
+    pub fn set_perks_only_applies_to_ranks()
+    {
+        let mut rank_list: RankList = Rank::new(1.into(), 1.into(), 0).into();
+        assert!(rank_list.set_perks(1.into(), Some("Unlocks #lounge".to_string())));
+        assert!(!rank_list.set_perks(2.into(), Some("Unlocks #nothing".to_string())));
+        assert_eq!(rank_list.perks(1.into()), Some("Unlocks #lounge"));
+        assert_eq!(rank_list.perks(2.into()), None);
+
+        assert!(rank_list.set_perks(1.into(), None));
+        assert_eq!(rank_list.perks(1.into()), None);
+    }
+
+    #[test]
+    pub fn relink_carries_perks_over()
+    {
+        let mut rank_list: RankList = Rank::new(1.into(), 1.into(), 0).into();
+        rank_list.set_perks(1.into(), Some("Unlocks #lounge".to_string()));
+        rank_list.relink(1.into(), 2.into()).unwrap();
+        assert_eq!(rank_list.perks(2.into()), Some("Unlocks #lounge"));
+        assert_eq!(rank_list.perks(1.into()), None);
+    }
+
+    #[test]
+    pub fn group_by_guild_with_no_ranks_is_empty()
+    {
+        assert!(RankList::group_by_guild(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    pub fn bot_cannot_manage_role_at_or_above_its_own()
+    {
+        assert!(can_bot_manage_role(5, 4));
+        assert!(!can_bot_manage_role(5, 5));
+        assert!(!can_bot_manage_role(5, 6));
+    }
+
     // Fuuuck we can't actually test saving for now... we really should mock PgPool or something...
+
+    // Only runs against a real database, since the rollback is Postgres doing its job.
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL"]
+    pub async fn save_rolls_back_the_delete_when_the_upsert_fails()
+    {
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set to run this test");
+        let db = PgPool::connect(&database_url).await.unwrap();
+        // A guild id no real server will have, so this can't touch anything else.
+        let guild_id = serenity::GuildId::new(4_000_000_001);
+        let guild: i64 = guild_id.into();
+        sqlx::query!("DELETE FROM rank_table WHERE guild_id = $1;", guild).execute(&db).await.unwrap();
+
+        let rank_list: RankList = vec![Rank::new(guild_id, 1.into(), 100), Rank::new(guild_id, 2.into(), 200)].as_slice().try_into().unwrap();
+        rank_list.save(&db).await.unwrap();
+
+        // Remove role 1 and move role 2 onto a threshold another row already has.
+        // The unique check fails when the transaction commits, after the delete already ran.
+        let mut rank_list = RankList::load(&db, guild_id).await.unwrap();
+        assert!(rank_list.remove_rank(Rank::new(guild_id, 1.into(), 0)));
+        rank_list.add_rank(Rank::new(guild_id, 2.into(), 300)).unwrap();
+        sqlx::query!("INSERT INTO rank_table (guild_id, role_id, minimum_word_count) VALUES ($1, 3, 300);", guild).execute(&db).await.unwrap();
+        assert!(rank_list.save(&db).await.is_err());
+
+        let saved = RankList::load(&db, guild_id).await.unwrap();
+        let counts: Vec<(RoleId, u32)> = saved.iter().map(|rank| (rank.rank_id.role_id, rank.minimum_word_count)).collect();
+        assert_eq!(counts, vec![(1.into(), 100), (2.into(), 200), (3.into(), 300)]);
+
+        sqlx::query!("DELETE FROM rank_table WHERE guild_id = $1;", guild).execute(&db).await.unwrap();
+    }
 }
@@ -0,0 +1,200 @@
+//! Helpers for working with Discord roles.
+//! This includes finding roles from what a user typed (slash commands get a proper role picker,
+//! but prefix commands only get text) and working out role colors.
+
+use poise::serenity_prelude as serenity;
+use thiserror::Error;
+
+/// Returned by [resolve_role_name] when a name doesn't point at exactly one role.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ResolveError
+{
+    #[error("No role matches \"{0}\"")]
+    NotFound(String),
+    #[error("\"{0}\" matches more than one role: {1:?}")]
+    Ambiguous(String, Vec<String>),
+}
+
+/// Finds the role a user meant by `name`, ignoring case.
+///
+/// An exact name match always wins. Otherwise, `name` can be the start of a role's name
+/// as long as only one role starts with it.
+///
+/// # Errors
+///
+/// [ResolveError::NotFound] - No role's name matches or starts with `name`.
+/// [ResolveError::Ambiguous] - More than one role has exactly this name, or there's no exact match
+/// and more than one role starts with `name`.
+///
+/// # Examples
+///
+/// ```
+/// # use progress_report_bot::role::resolve_role_name;
+/// # use poise::serenity_prelude::RoleId;
+/// let roles = vec![(RoleId::new(1), "Novelist".to_string()), (RoleId::new(2), "Poet".to_string())];
+/// assert_eq!(resolve_role_name("nov", &roles), Ok(RoleId::new(1)));
+/// ```
+pub fn resolve_role_name(name: &str, roles: &[(serenity::RoleId, String)]) -> Result<serenity::RoleId, ResolveError>
+{
+    // Errors echo the name as it was typed, only the matching ignores case.
+    let name = name.trim();
+    let lowercase_name = name.to_lowercase();
+
+    let exact_matches: Vec<&(serenity::RoleId, String)> = roles.iter()
+        .filter(|(_, role_name)| role_name.to_lowercase() == lowercase_name)
+        .collect();
+    let matches = match exact_matches.as_slice()
+    {
+        [] => roles.iter()
+            .filter(|(_, role_name)| role_name.to_lowercase().starts_with(&lowercase_name))
+            .collect(),
+        _ => exact_matches,
+    };
+
+    match matches.as_slice()
+    {
+        [] => Err(ResolveError::NotFound(name.to_string())),
+        [(role_id, _)] => Ok(*role_id),
+        _ => Err(ResolveError::Ambiguous(name.to_string(), matches.iter().map(|(_, role_name)| role_name.clone()).collect())),
+    }
+}
+
+/// Parses a hex color like "#ff8800" or "ff8800".
+pub fn parse_hex_color(color: &str) -> Option<serenity::Colour>
+{
+    let hex = color.trim().trim_start_matches('#');
+    // from_str_radix would also accept a leading '+', so check the digits ourselves.
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok().map(serenity::Colour::new)
+}
+
+/// Gets `n` colors evenly spaced between `from` and `to`, including both ends.
+/// With one color you just get `from`.
+pub fn interpolate_colors(from: serenity::Colour, to: serenity::Colour, n: usize) -> Vec<serenity::Colour>
+{
+    if n == 1
+    {
+        return vec![from];
+    }
+
+    let lerp = |start: u8, end: u8, i: usize| -> u8 {
+        let start = start as f32;
+        let end = end as f32;
+        (start + (end - start) * i as f32 / (n - 1) as f32).round() as u8
+    };
+
+    (0..n)
+        .map(|i| serenity::Colour::from_rgb(
+            lerp(from.r(), to.r(), i),
+            lerp(from.g(), to.g(), i),
+            lerp(from.b(), to.b(), i),
+        ))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn roles() -> Vec<(serenity::RoleId, String)>
+    {
+        vec![
+            (serenity::RoleId::new(1), "Novelist".to_string()),
+            (serenity::RoleId::new(2), "Novel".to_string()),
+            (serenity::RoleId::new(3), "Poet".to_string()),
+            (serenity::RoleId::new(4), "Poet Laureate".to_string()),
+            (serenity::RoleId::new(5), "Scribe".to_string()),
+        ]
+    }
+
+    #[test]
+    pub fn resolve_exact_match_ignores_case()
+    {
+        assert_eq!(resolve_role_name("novel", &roles()), Ok(serenity::RoleId::new(2)));
+        assert_eq!(resolve_role_name("POET", &roles()), Ok(serenity::RoleId::new(3)));
+    }
+
+    #[test]
+    pub fn resolve_unique_prefix()
+    {
+        assert_eq!(resolve_role_name("scr", &roles()), Ok(serenity::RoleId::new(5)));
+        assert_eq!(resolve_role_name("Poet L", &roles()), Ok(serenity::RoleId::new(4)));
+    }
+
+    #[test]
+    pub fn resolve_ambiguous_prefix_fails()
+    {
+        let result = resolve_role_name("nov", &roles());
+        assert!(matches!(result, Err(ResolveError::Ambiguous(_, ref names)) if names.len() == 2));
+    }
+
+    #[test]
+    pub fn resolve_unknown_name_fails()
+    {
+        assert_eq!(resolve_role_name("editor", &roles()), Err(ResolveError::NotFound("editor".to_string())));
+    }
+
+    #[test]
+    pub fn resolve_unknown_name_keeps_typed_case()
+    {
+        assert_eq!(resolve_role_name(" Editor ", &roles()), Err(ResolveError::NotFound("Editor".to_string())));
+    }
+
+    #[test]
+    pub fn resolve_duplicate_exact_names_fails()
+    {
+        let mut roles = roles();
+        roles.push((serenity::RoleId::new(6), "poet".to_string()));
+        assert_eq!(
+            resolve_role_name("Poet", &roles),
+            Err(ResolveError::Ambiguous("Poet".to_string(), vec!["Poet".to_string(), "poet".to_string()]))
+        );
+    }
+
+    #[test]
+    pub fn interpolate_colors_evenly_spaced()
+    {
+        let from = serenity::Colour::from_rgb(0, 0, 0);
+        let to = serenity::Colour::from_rgb(200, 100, 40);
+        let colors = interpolate_colors(from, to, 5);
+        assert_eq!(colors, vec![
+            serenity::Colour::from_rgb(0, 0, 0),
+            serenity::Colour::from_rgb(50, 25, 10),
+            serenity::Colour::from_rgb(100, 50, 20),
+            serenity::Colour::from_rgb(150, 75, 30),
+            serenity::Colour::from_rgb(200, 100, 40),
+        ]);
+    }
+
+    #[test]
+    pub fn interpolate_colors_going_down()
+    {
+        let from = serenity::Colour::from_rgb(255, 255, 255);
+        let to = serenity::Colour::from_rgb(55, 155, 255);
+        let colors = interpolate_colors(from, to, 3);
+        assert_eq!(colors[1], serenity::Colour::from_rgb(155, 205, 255));
+    }
+
+    #[test]
+    pub fn interpolate_colors_single_and_empty()
+    {
+        let from = serenity::Colour::from_rgb(10, 20, 30);
+        let to = serenity::Colour::from_rgb(40, 50, 60);
+        assert_eq!(interpolate_colors(from, to, 1), vec![from]);
+        assert!(interpolate_colors(from, to, 0).is_empty());
+    }
+
+    #[test]
+    pub fn parse_hex_colors()
+    {
+        assert_eq!(parse_hex_color("#ff8800"), Some(serenity::Colour::from_rgb(255, 136, 0)));
+        assert_eq!(parse_hex_color("00FF00"), Some(serenity::Colour::from_rgb(0, 255, 0)));
+        assert_eq!(parse_hex_color("#fff"), None);
+        assert_eq!(parse_hex_color("orange"), None);
+        assert_eq!(parse_hex_color("#+ff880"), None);
+    }
+}
@@ -1,6 +1,15 @@
 //! This module handles parsing and describing word count, including total vs relative.
 
+use std::fmt::Display;
+use std::ops::{Add, Sub};
 use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, TimeDelta, Utc};
+use getset::CopyGetters;
+use poise::serenity_prelude as serenity;
+use sqlx::PgPool;
+use thiserror::Error;
 
 /// Represents a parsed word count argument, which can either be relative or overall.
 /// If a number parsed by WordCountArgument starts with '+' or '-' it is treated as relative,
@@ -12,33 +21,160 @@ pub enum WordCountArgument
     Total(u32),
 }
 
+/// Returned by [WordCountArgument::from_str] when the input isn't a word count.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WordCountParseError
+{
+    #[error("No word count was given")]
+    Empty,
+    #[error("\"{0}\" isn't a valid word count")]
+    InvalidDigits(String),
+    #[error("A word count can only have one + or - sign")]
+    MultipleSigns,
+    #[error("\"{0}\" is too big to be a word count")]
+    TooLarge(String),
+}
+
 impl FromStr for WordCountArgument
 {
-    type Err = anyhow::Error;
+    type Err = WordCountParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // People paste numbers with stray spaces around them, so trim before looking for the sign.
+        let s = strip_unit_label(s.trim());
+        if s.is_empty()
+        {
+            return Err(WordCountParseError::Empty);
+        }
+
         let mut relative = false;
         let mut remainder = s;
         if s.starts_with("+") || s.starts_with("-")
         {
             relative = true;
-            remainder = &s[1..];
+            // Trim again so a second sign after a space, like "+ -5", is still caught below.
+            remainder = s[1..].trim_start();
         }
-        // We filter out any commas so that numbers formatted like 1,234 
-        // Don't break the parser.
-        let parsed_remainder: u32 = remainder.chars().filter(|x| *x != ',').collect::<String>().parse()?;
+        if remainder.starts_with("+") || remainder.starts_with("-")
+        {
+            return Err(WordCountParseError::MultipleSigns);
+        }
+
+        let parsed_remainder = parse_magnitude(remainder)?;
         Ok(match relative
         {
-            true => Self::Relative(match s.chars().nth(0).unwrap() {
-                '+' => parsed_remainder.try_into().unwrap(),
-                '-' => TryInto::<i32>::try_into(parsed_remainder).unwrap() * -1,
-                _ => unreachable!()
-            }),
+            true => {
+                let signed = match s.chars().nth(0).unwrap() {
+                    '+' => parsed_remainder as i64,
+                    '-' => -(parsed_remainder as i64),
+                    _ => unreachable!()
+                };
+                Self::Relative(signed.try_into().map_err(|_| WordCountParseError::TooLarge(s.to_string()))?)
+            },
             false => Self::Total(parsed_remainder),
         })
     }
 }
 
+impl Display for WordCountArgument
+{
+    /// Relative counts always show their sign so they parse back as relative,
+    /// and both forms use thousands separators (which [WordCountArgument::from_str] ignores).
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self
+        {
+            Self::Relative(x) if *x < 0 => write!(f, "-{}", group_thousands(x.unsigned_abs() as u64)),
+            Self::Relative(x) => write!(f, "+{}", group_thousands(*x as u64)),
+            Self::Total(x) => write!(f, "{}", group_thousands(*x as u64)),
+        }
+    }
+}
+
+/// Formats a number with commas between each group of three digits, like 1,234,567.
+pub fn group_thousands(n: u64) -> String
+{
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate()
+    {
+        if i > 0 && (digits.len() - i).is_multiple_of(3)
+        {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Labels people leave on the end of a word count when copying it out of a writing app,
+/// like "12,345 words". Longer labels come first so "words" isn't stripped as just "word".
+const UNIT_LABELS: [&str; 3] = ["words", "word", "wc"];
+
+/// Strips one trailing [UNIT_LABELS] entry (case-insensitively) and any whitespace before it.
+fn strip_unit_label(s: &str) -> &str
+{
+    for label in UNIT_LABELS
+    {
+        // Check the boundary first so split_at can't panic on input ending in a multi-byte character.
+        if s.len() >= label.len() && s.is_char_boundary(s.len() - label.len())
+        {
+            let (rest, tail) = s.split_at(s.len() - label.len());
+            if tail.eq_ignore_ascii_case(label)
+            {
+                return rest.trim_end();
+            }
+        }
+    }
+    s
+}
+
+/// Parses the unsigned part of a word count, like "1,234" or "1.5k".
+///
+/// A trailing 'k' or 'm' (in either case) multiplies the number by a thousand or a million.
+/// Decimals are only allowed with a suffix, and only if they come out to a whole number of words.
+fn parse_magnitude(s: &str) -> Result<u32, WordCountParseError>
+{
+    // We filter out any commas and spaces so that numbers formatted like 1,234 or 12 345
+    // Don't break the parser.
+    let s: String = s.chars().filter(|x| *x != ',' && !x.is_whitespace()).collect();
+    if s.is_empty()
+    {
+        return Err(WordCountParseError::Empty);
+    }
+
+    let invalid = || WordCountParseError::InvalidDigits(s.clone());
+    let too_large = || WordCountParseError::TooLarge(s.clone());
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+
+    let (number, multiplier): (&str, u64) = match s.char_indices().last()
+    {
+        Some((i, 'k' | 'K')) => (&s[..i], 1_000),
+        Some((i, 'm' | 'M')) => (&s[..i], 1_000_000),
+        _ if is_digits(&s) => return s.parse().map_err(|_| too_large()),
+        _ => return Err(invalid()),
+    };
+
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction)
+    {
+        return Err(invalid());
+    }
+
+    let whole: u64 = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| too_large())? };
+    let fraction_value: u64 = if fraction.is_empty() { 0 } else { fraction.parse().map_err(|_| invalid())? };
+    // The fraction has to work out to whole words, so 1.5k is fine but 1.2345k isn't.
+    let fraction_words = 10u64.checked_pow(fraction.len() as u32)
+        .and_then(|scale| fraction_value.checked_mul(multiplier).map(|scaled| (scaled, scale)))
+        .filter(|(scaled, scale)| scaled % scale == 0)
+        .map(|(scaled, scale)| scaled / scale)
+        .ok_or_else(invalid)?;
+
+    let total = whole.checked_mul(multiplier)
+        .and_then(|whole_words| whole_words.checked_add(fraction_words))
+        .ok_or_else(too_large)?;
+    total.try_into().map_err(|_| too_large())
+}
+
 impl WordCountArgument
 {
     /// Converts a relative or total word count into only a total word count.
@@ -64,25 +200,94 @@ impl WordCountArgument
     /// ```
     pub fn convert_to_total(&self, current_word_count: u32) -> TotalWordCount
     {
+        let current_word_count = TotalWordCount(current_word_count);
         match self
         {
-            Self::Relative(x) => TotalWordCount(
-                i32::max(x + (current_word_count as i32), 0).try_into().unwrap()
-            ),
+            // TotalWordCount's Add and Sub saturate, so this can't overflow either way.
+            Self::Relative(x) if *x >= 0 => current_word_count + TotalWordCount(x.unsigned_abs()),
+            Self::Relative(x) => current_word_count - TotalWordCount(x.unsigned_abs()),
             Self::Total(x) => TotalWordCount(*x)
         }
     }
 }
 
 /// Represents a project's/user's total word count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TotalWordCount(u32);
 
+impl Display for TotalWordCount
+{
+    /// Uses thousands separators, the same as [WordCountArgument]'s Display.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", group_thousands(self.0.into()))
+    }
+}
+
+impl Add for TotalWordCount
+{
+    type Output = Self;
+
+    /// Saturates at [u32::MAX] rather than overflowing.
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for TotalWordCount
+{
+    type Output = Self;
+
+    /// Saturates at 0, since a total can't go negative.
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
 impl TotalWordCount
 {
     pub fn word_count(&self) -> u32
     {
         self.0
     }
+
+    /// Estimates how long it would take to read this many words at `words_per_minute`.
+    /// Returns None if `words_per_minute` is 0, since you'd never finish.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::str::FromStr;
+    /// # use std::time::Duration;
+    /// # use progress_report_bot::word_count::TotalWordCount;
+    /// let total = TotalWordCount::from_str("2,500").unwrap();
+    /// assert_eq!(total.reading_time(250), Some(Duration::from_secs(10 * 60)));
+    /// assert_eq!(total.reading_time(0), None);
+    /// ```
+    pub fn reading_time(&self, words_per_minute: u32) -> Option<Duration>
+    {
+        // Work in seconds so small totals don't round down to nothing.
+        (self.0 as u64 * 60)
+            .checked_div(words_per_minute as u64)
+            .map(Duration::from_secs)
+    }
+}
+
+/// Formats a reading time like "about 4 hours 12 minutes".
+pub fn format_reading_time(reading_time: Duration) -> String
+{
+    let total_minutes = reading_time.as_secs() / 60;
+    let hours = total_minutes / 60;
+    let minutes = total_minutes % 60;
+
+    let plural = |count: u64, unit: &str| if count == 1 { format!("1 {}", unit) } else { format!("{} {}s", count, unit) };
+
+    match (hours, minutes)
+    {
+        (0, 0) => "less than a minute".to_string(),
+        (0, minutes) => format!("about {}", plural(minutes, "minute")),
+        (hours, 0) => format!("about {}", plural(hours, "hour")),
+        (hours, minutes) => format!("about {} {}", plural(hours, "hour"), plural(minutes, "minute")),
+    }
 }
 
 impl FromStr for TotalWordCount
@@ -96,6 +301,309 @@ impl FromStr for TotalWordCount
     }
 }
 
+/// A user's total word count in a guild, as stored in the database.
+#[derive(Debug, CopyGetters)]
+pub struct UserWordCount
+{
+    #[getset(get_copy = "pub")]
+    guild_id: serenity::GuildId,
+    #[getset(get_copy = "pub")]
+    user_id: serenity::UserId,
+    word_count: TotalWordCount,
+}
+
+/// Internal representation of the database record
+struct DbUserWordCount
+{
+    word_count: i64,
+}
+
+/// Internal representation of a leaderboard row
+struct DbLeaderboardEntry
+{
+    user_id: i64,
+    word_count: i64,
+}
+
+impl UserWordCount
+{
+    /// Loads a user's word count from the database.
+    /// Users who have never reported don't have a record, so they get a word count of 0.
+    pub async fn load(db: &PgPool, guild_id: serenity::GuildId, user_id: serenity::UserId) -> anyhow::Result<Self>
+    {
+        let db_guild_id: i64 = guild_id.into();
+        let db_user_id: i64 = user_id.into();
+
+        let record = sqlx::query_as!(DbUserWordCount, "SELECT word_count FROM user_word_count WHERE guild_id = $1 AND user_id = $2;", db_guild_id, db_user_id)
+            .fetch_optional(db)
+            .await?;
+
+        Self::from_record(guild_id, user_id, record)
+    }
+
+    /// Builds a UserWordCount from an optional database record, defaulting to 0 if there isn't one.
+    fn from_record(guild_id: serenity::GuildId, user_id: serenity::UserId, record: Option<DbUserWordCount>) -> anyhow::Result<Self>
+    {
+        let word_count = match record
+        {
+            Some(record) => TotalWordCount(record.word_count.try_into()?),
+            None => TotalWordCount(0),
+        };
+
+        Ok(Self {
+            guild_id,
+            user_id,
+            word_count,
+        })
+    }
+
+    /// Loads the `limit` highest word counts in a guild, highest first.
+    /// Ties are broken by user id so the order is stable.
+    pub async fn top(db: &PgPool, guild_id: serenity::GuildId, limit: u32) -> anyhow::Result<Vec<Self>>
+    {
+        let db_guild_id: i64 = guild_id.into();
+        let limit: i64 = limit.into();
+
+        let records = sqlx::query_as!(DbLeaderboardEntry, "SELECT user_id, word_count FROM user_word_count WHERE guild_id = $1 ORDER BY word_count DESC, user_id LIMIT $2;", db_guild_id, limit)
+            .fetch_all(db)
+            .await?;
+
+        records.into_iter()
+            .map(|record| Self::from_record(guild_id, serenity::UserId::new(record.user_id as u64), Some(DbUserWordCount { word_count: record.word_count })))
+            .collect()
+    }
+
+    /// Saves this word count to the database, replacing whatever was there.
+    /// The new total is also added to the user's history in word_count_log.
+    pub async fn save(&self, db: &PgPool) -> anyhow::Result<()>
+    {
+        let guild_id: i64 = self.guild_id.into();
+        let user_id: i64 = self.user_id.into();
+        let word_count: i64 = self.word_count.word_count().into();
+
+        let mut transaction = db.begin().await?;
+        sqlx::query!("INSERT INTO user_word_count (guild_id, user_id, word_count) VALUES ($1, $2, $3) ON CONFLICT (guild_id, user_id) DO UPDATE SET word_count = excluded.word_count;", guild_id, user_id, word_count)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query!("INSERT INTO word_count_log (guild_id, user_id, word_count) VALUES ($1, $2, $3);", guild_id, user_id, word_count)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    /// Loads every total the user reported at or after `since`, plus the last one before it
+    /// to compare against, newest first. See [words_since].
+    pub async fn history_since(&self, db: &PgPool, since: DateTime<Utc>) -> anyhow::Result<Vec<WordCountLogEntry>>
+    {
+        let guild_id: i64 = self.guild_id.into();
+        let user_id: i64 = self.user_id.into();
+
+        let records = sqlx::query_as!(DbWordCountLogEntry, r#"
+            SELECT word_count AS "word_count!", created_at AS "created_at!" FROM (
+                (SELECT word_count, created_at, id FROM word_count_log WHERE guild_id = $1 AND user_id = $2 AND created_at >= $3)
+                UNION ALL
+                (SELECT word_count, created_at, id FROM word_count_log WHERE guild_id = $1 AND user_id = $2 AND created_at < $3 ORDER BY created_at DESC, id DESC LIMIT 1)
+            ) AS recent
+            ORDER BY created_at DESC, id DESC;"#, guild_id, user_id, since)
+            .fetch_all(db)
+            .await?;
+
+        records.into_iter()
+            .map(|record| Ok(WordCountLogEntry {
+                word_count: TotalWordCount(record.word_count.try_into()?),
+                created_at: record.created_at,
+            }))
+            .collect()
+    }
+
+    /// Loads the user's last `limit` reported totals, newest first.
+    /// Users who have never reported get an empty list.
+    pub async fn history(&self, db: &PgPool, limit: u32) -> anyhow::Result<Vec<WordCountLogEntry>>
+    {
+        let guild_id: i64 = self.guild_id.into();
+        let user_id: i64 = self.user_id.into();
+        let limit: i64 = limit.into();
+
+        let records = sqlx::query_as!(DbWordCountLogEntry, "SELECT word_count, created_at FROM word_count_log WHERE guild_id = $1 AND user_id = $2 ORDER BY created_at DESC, id DESC LIMIT $3;", guild_id, user_id, limit)
+            .fetch_all(db)
+            .await?;
+
+        records.into_iter()
+            .map(|record| Ok(WordCountLogEntry {
+                word_count: TotalWordCount(record.word_count.try_into()?),
+                created_at: record.created_at,
+            }))
+            .collect()
+    }
+
+    pub fn word_count(&self) -> TotalWordCount
+    {
+        self.word_count
+    }
+
+    pub fn set_word_count(&mut self, word_count: TotalWordCount)
+    {
+        self.word_count = word_count;
+    }
+}
+
+/// One reported total from a user's history.
+///
+/// Totals from before history was kept were backfilled with a `created_at` of the Unix epoch,
+/// see [WordCountLogEntry::is_from_before_history].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct WordCountLogEntry
+{
+    word_count: TotalWordCount,
+    created_at: DateTime<Utc>,
+}
+
+impl WordCountLogEntry
+{
+    /// Returns true if this is a user's total from before history was kept,
+    /// so its `created_at` isn't when it was actually reported.
+    pub fn is_from_before_history(&self) -> bool
+    {
+        self.created_at == DateTime::UNIX_EPOCH
+    }
+}
+
+/// Internal representation of the database record
+struct DbWordCountLogEntry
+{
+    word_count: i64,
+    created_at: DateTime<Utc>,
+}
+
+/// Works out how much each entry changed the total from the one before it.
+/// `entries` should be newest first, like from [UserWordCount::history].
+///
+/// The oldest entry has nothing to compare to, so its change is None.
+pub fn history_deltas(entries: &[WordCountLogEntry]) -> Vec<Option<i64>>
+{
+    entries.iter()
+        .enumerate()
+        .map(|(i, entry)| entries.get(i + 1)
+            .map(|previous| entry.word_count.word_count() as i64 - previous.word_count.word_count() as i64))
+        .collect()
+}
+
+/// Works out how many words were written at or after `since` from a user's history.
+/// `entries` should be newest first and include the last entry before `since`, like from
+/// [UserWordCount::history_since]. That entry is the starting point, and for users who had a
+/// total before history was kept it's at least their backfilled one.
+/// If there isn't one, the user's first report counts from 0.
+///
+/// Returns 0 if nothing was reported since `since`. Deleting words counts as negative.
+pub fn words_since(entries: &[WordCountLogEntry], since: DateTime<Utc>) -> i64
+{
+    let Some(latest) = entries.first().filter(|entry| entry.created_at >= since) else
+    {
+        return 0;
+    };
+    let start = entries.iter()
+        .find(|entry| entry.created_at < since)
+        .map_or(0, |entry| entry.word_count.word_count());
+
+    latest.word_count.word_count() as i64 - start as i64
+}
+
+/// Gets when "today" and "this week" started, as of `now`.
+/// Everything is in UTC, so days start at midnight UTC and weeks start on Monday at midnight UTC,
+/// whatever timezone the user is in.
+pub fn day_and_week_start(now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>)
+{
+    let day_start = now.date_naive().and_hms_opt(0, 0, 0).expect("Midnight is always a valid time").and_utc();
+    let week_start = day_start - TimeDelta::days(now.weekday().num_days_from_monday().into());
+    (day_start, week_start)
+}
+
+/// A user's personal word count goal in a guild, as stored in the database.
+#[derive(Debug, CopyGetters)]
+pub struct UserGoal
+{
+    #[getset(get_copy = "pub")]
+    guild_id: serenity::GuildId,
+    #[getset(get_copy = "pub")]
+    user_id: serenity::UserId,
+    goal: Option<TotalWordCount>,
+}
+
+/// Internal representation of the database record
+struct DbUserGoal
+{
+    goal: i64,
+}
+
+impl UserGoal
+{
+    /// Loads a user's goal from the database. Users who haven't set one get None.
+    pub async fn load(db: &PgPool, guild_id: serenity::GuildId, user_id: serenity::UserId) -> anyhow::Result<Self>
+    {
+        let db_guild_id: i64 = guild_id.into();
+        let db_user_id: i64 = user_id.into();
+
+        let record = sqlx::query_as!(DbUserGoal, "SELECT goal FROM goals WHERE guild_id = $1 AND user_id = $2;", db_guild_id, db_user_id)
+            .fetch_optional(db)
+            .await?;
+
+        Ok(Self {
+            guild_id,
+            user_id,
+            goal: record.map(|record| record.goal.try_into().map(TotalWordCount)).transpose()?,
+        })
+    }
+
+    /// Saves this goal to the database, replacing whatever was there.
+    /// If the goal has been cleared, the record is deleted instead.
+    pub async fn save(&self, db: &PgPool) -> anyhow::Result<()>
+    {
+        let guild_id: i64 = self.guild_id.into();
+        let user_id: i64 = self.user_id.into();
+
+        match self.goal
+        {
+            Some(goal) =>
+            {
+                let goal: i64 = goal.word_count().into();
+                sqlx::query!("INSERT INTO goals (guild_id, user_id, goal) VALUES ($1, $2, $3) ON CONFLICT (guild_id, user_id) DO UPDATE SET goal = excluded.goal;", guild_id, user_id, goal)
+                    .execute(db)
+                    .await?;
+            }
+            None =>
+            {
+                sqlx::query!("DELETE FROM goals WHERE guild_id = $1 AND user_id = $2;", guild_id, user_id)
+                    .execute(db)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn goal(&self) -> Option<TotalWordCount>
+    {
+        self.goal
+    }
+
+    /// Sets the goal. Use None to clear it.
+    pub fn set_goal(&mut self, goal: Option<TotalWordCount>)
+    {
+        self.goal = goal;
+    }
+}
+
+/// Describes progress toward a goal, like "1,500 / 5,000 (30%)".
+/// The percentage is rounded down and can go over 100% once the goal is passed.
+pub fn format_goal_progress(word_count: TotalWordCount, goal: TotalWordCount) -> String
+{
+    let percent = (word_count.word_count() as u64 * 100).checked_div(goal.word_count() as u64).unwrap_or(100);
+    format!("{} / {} ({}%)", word_count, goal, percent)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,6 +643,188 @@ mod tests {
         assert_eq!(wc, WordCountArgument::Relative(12999));
     }
 
+    #[test]
+    pub fn test_parse_word_count_total_with_suffix()
+    {
+        assert_eq!(WordCountArgument::from_str("50k").unwrap(), WordCountArgument::Total(50000));
+        assert_eq!(WordCountArgument::from_str("50K").unwrap(), WordCountArgument::Total(50000));
+        assert_eq!(WordCountArgument::from_str("2m").unwrap(), WordCountArgument::Total(2000000));
+        assert_eq!(WordCountArgument::from_str("1.25M").unwrap(), WordCountArgument::Total(1250000));
+    }
+
+    #[test]
+    pub fn test_parse_word_count_relative_with_suffix()
+    {
+        assert_eq!(WordCountArgument::from_str("+1.5k").unwrap(), WordCountArgument::Relative(1500));
+        assert_eq!(WordCountArgument::from_str("+2k").unwrap(), WordCountArgument::Relative(2000));
+        assert_eq!(WordCountArgument::from_str("-10k").unwrap(), WordCountArgument::Relative(-10000));
+        assert_eq!(WordCountArgument::from_str("+1m").unwrap(), WordCountArgument::Relative(1000000));
+    }
+
+    #[test]
+    pub fn test_parse_word_count_suffix_with_commas()
+    {
+        assert_eq!(WordCountArgument::from_str("1,500k").unwrap(), WordCountArgument::Total(1500000));
+    }
+
+    #[test]
+    pub fn test_parse_ambiguous_suffix_fails()
+    {
+        assert!(WordCountArgument::from_str("5kk").is_err());
+        assert!(WordCountArgument::from_str("k").is_err());
+        assert!(WordCountArgument::from_str("+m").is_err());
+        assert!(WordCountArgument::from_str("5mk").is_err());
+    }
+
+    #[test]
+    pub fn test_parse_fractional_words_fails()
+    {
+        assert!(WordCountArgument::from_str("1.2345k").is_err());
+        assert!(WordCountArgument::from_str("1.5").is_err());
+    }
+
+    #[test]
+    pub fn test_parse_suffix_too_big_fails()
+    {
+        assert!(WordCountArgument::from_str("5000m").is_err());
+        assert!(WordCountArgument::from_str("+3000m").is_err());
+    }
+
+    #[test]
+    pub fn test_display_total_word_count()
+    {
+        assert_eq!(TotalWordCount(0).to_string(), "0");
+        assert_eq!(TotalWordCount(999).to_string(), "999");
+        assert_eq!(TotalWordCount(1234567).to_string(), "1,234,567");
+    }
+
+    #[test]
+    pub fn test_total_word_count_add()
+    {
+        assert_eq!(TotalWordCount(1200) + TotalWordCount(34), TotalWordCount(1234));
+        assert_eq!(TotalWordCount(u32::MAX) + TotalWordCount(1), TotalWordCount(u32::MAX));
+    }
+
+    #[test]
+    pub fn test_total_word_count_sub_saturates()
+    {
+        assert_eq!(TotalWordCount(500) - TotalWordCount(200), TotalWordCount(300));
+        assert_eq!(TotalWordCount(200) - TotalWordCount(500), TotalWordCount(0));
+    }
+
+    #[test]
+    pub fn test_total_word_count_ordering()
+    {
+        assert!(TotalWordCount(100) < TotalWordCount(101));
+        assert_eq!(TotalWordCount(7).max(TotalWordCount(3)), TotalWordCount(7));
+
+        let mut totals = vec![TotalWordCount(50), TotalWordCount(5000), TotalWordCount(0)];
+        totals.sort();
+        assert_eq!(totals, vec![TotalWordCount(0), TotalWordCount(50), TotalWordCount(5000)]);
+    }
+
+    #[test]
+    pub fn test_display_word_count()
+    {
+        assert_eq!(WordCountArgument::Relative(100).to_string(), "+100");
+        assert_eq!(WordCountArgument::Relative(-50).to_string(), "-50");
+        assert_eq!(WordCountArgument::Relative(0).to_string(), "+0");
+        assert_eq!(WordCountArgument::Relative(-12345).to_string(), "-12,345");
+        assert_eq!(WordCountArgument::Total(1234).to_string(), "1,234");
+        assert_eq!(WordCountArgument::Total(123).to_string(), "123");
+        assert_eq!(WordCountArgument::Total(1234567).to_string(), "1,234,567");
+    }
+
+    #[test]
+    pub fn test_display_round_trip()
+    {
+        for input in ["1234", "0", "+50", "-1579", "123,456", "+12,999", "50k", "-2m", "4294967295"]
+        {
+            let wc = WordCountArgument::from_str(input).unwrap();
+            let reparsed = WordCountArgument::from_str(&wc.to_string()).unwrap();
+            assert_eq!(wc, reparsed, "{} didn't survive a round trip", input);
+        }
+    }
+
+    #[test]
+    pub fn test_parse_word_count_total_with_whitespace()
+    {
+        assert_eq!(WordCountArgument::from_str(" 1234 ").unwrap(), WordCountArgument::Total(1234));
+        assert_eq!(WordCountArgument::from_str("\t500\n").unwrap(), WordCountArgument::Total(500));
+    }
+
+    #[test]
+    pub fn test_parse_word_count_relative_with_whitespace()
+    {
+        assert_eq!(WordCountArgument::from_str(" +50 ").unwrap(), WordCountArgument::Relative(50));
+        assert_eq!(WordCountArgument::from_str("  -20").unwrap(), WordCountArgument::Relative(-20));
+    }
+
+    #[test]
+    pub fn test_parse_word_count_with_space_separators()
+    {
+        assert_eq!(WordCountArgument::from_str("12 345").unwrap(), WordCountArgument::Total(12345));
+        assert_eq!(WordCountArgument::from_str("+1 234 567").unwrap(), WordCountArgument::Relative(1234567));
+    }
+
+    #[test]
+    pub fn test_parse_whitespace_only_fails()
+    {
+        assert!(WordCountArgument::from_str("   ").is_err());
+    }
+
+    #[test]
+    pub fn test_parse_empty_is_empty_error()
+    {
+        assert_eq!(WordCountArgument::from_str(""), Err(WordCountParseError::Empty));
+        assert_eq!(WordCountArgument::from_str("+"), Err(WordCountParseError::Empty));
+    }
+
+    #[test]
+    pub fn test_parse_letters_is_invalid_digits_error()
+    {
+        assert_eq!(WordCountArgument::from_str("abc"), Err(WordCountParseError::InvalidDigits("abc".to_string())));
+    }
+
+    #[test]
+    pub fn test_parse_word_count_with_unit_label()
+    {
+        assert_eq!(WordCountArgument::from_str("500 words").unwrap(), WordCountArgument::Total(500));
+        assert_eq!(WordCountArgument::from_str("+200 wc").unwrap(), WordCountArgument::Relative(200));
+        assert_eq!(WordCountArgument::from_str("12,345 Words").unwrap(), WordCountArgument::Total(12345));
+        assert_eq!(WordCountArgument::from_str("1 word").unwrap(), WordCountArgument::Total(1));
+        assert_eq!(WordCountArgument::from_str("1.5kWC ").unwrap(), WordCountArgument::Total(1500));
+    }
+
+    #[test]
+    pub fn test_parse_unit_label_without_number_fails()
+    {
+        assert_eq!(WordCountArgument::from_str("words"), Err(WordCountParseError::Empty));
+        assert_eq!(WordCountArgument::from_str("some words"), Err(WordCountParseError::InvalidDigits("some".to_string())));
+        assert!(WordCountArgument::from_str("500 words words").is_err());
+    }
+
+    #[test]
+    pub fn test_parse_multiple_signs_error()
+    {
+        assert_eq!(WordCountArgument::from_str("+-5"), Err(WordCountParseError::MultipleSigns));
+        assert_eq!(WordCountArgument::from_str("++5"), Err(WordCountParseError::MultipleSigns));
+        assert_eq!(WordCountArgument::from_str("+ -5"), Err(WordCountParseError::MultipleSigns));
+    }
+
+    #[test]
+    pub fn test_parse_too_large_error()
+    {
+        assert!(matches!(WordCountArgument::from_str("99999999999"), Err(WordCountParseError::TooLarge(_))));
+        assert!(matches!(WordCountArgument::from_str("+3000000000"), Err(WordCountParseError::TooLarge(_))));
+    }
+
+    #[test]
+    pub fn test_parse_relative_minimum()
+    {
+        assert_eq!(WordCountArgument::from_str("-2147483648").unwrap(), WordCountArgument::Relative(i32::MIN));
+    }
+
     #[test]
     pub fn test_parse_invalid_string_fails()
     {
@@ -173,5 +863,164 @@ mod tests {
         let total = wc.convert_to_total(100);
         assert_eq!(total.word_count(), 0);
     }
-}
 
+    #[test]
+    pub fn test_convert_relative_clamps_at_max()
+    {
+        let wc = WordCountArgument::Relative(100);
+        let total = wc.convert_to_total(u32::MAX);
+        assert_eq!(total.word_count(), u32::MAX);
+
+        let wc = WordCountArgument::Relative(i32::MAX);
+        let total = wc.convert_to_total(u32::MAX - 10);
+        assert_eq!(total.word_count(), u32::MAX);
+    }
+
+    #[test]
+    pub fn test_convert_relative_large_count_does_not_wrap()
+    {
+        // Above i32::MAX, so the old `as i32` cast would have gone negative.
+        let wc = WordCountArgument::Relative(500);
+        let total = wc.convert_to_total(3_000_000_000);
+        assert_eq!(total.word_count(), 3_000_000_500);
+    }
+
+    #[test]
+    pub fn test_convert_relative_large_negative_clamps_to_zero()
+    {
+        let wc = WordCountArgument::Relative(i32::MIN);
+        let total = wc.convert_to_total(5);
+        assert_eq!(total.word_count(), 0);
+    }
+
+    #[test]
+    pub fn test_reading_time()
+    {
+        let total = TotalWordCount(63000);
+        assert_eq!(total.reading_time(250), Some(Duration::from_secs(252 * 60)));
+        assert_eq!(TotalWordCount(100).reading_time(200), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    pub fn test_reading_time_zero_wpm_is_none()
+    {
+        assert_eq!(TotalWordCount(1000).reading_time(0), None);
+    }
+
+    #[test]
+    pub fn test_format_reading_time()
+    {
+        assert_eq!(format_reading_time(Duration::from_secs(252 * 60)), "about 4 hours 12 minutes");
+        assert_eq!(format_reading_time(Duration::from_secs(60 * 60)), "about 1 hour");
+        assert_eq!(format_reading_time(Duration::from_secs(61 * 60)), "about 1 hour 1 minute");
+        assert_eq!(format_reading_time(Duration::from_secs(30)), "less than a minute");
+    }
+
+    #[test]
+    pub fn test_user_word_count_without_record_is_zero()
+    {
+        let user_word_count = UserWordCount::from_record(1.into(), 2.into(), None).unwrap();
+        assert_eq!(user_word_count.word_count().word_count(), 0);
+        assert_eq!(user_word_count.guild_id(), serenity::GuildId::new(1));
+        assert_eq!(user_word_count.user_id(), serenity::UserId::new(2));
+    }
+
+    #[test]
+    pub fn test_user_word_count_from_record()
+    {
+        let record = DbUserWordCount { word_count: 3_000_000_000 };
+        let user_word_count = UserWordCount::from_record(1.into(), 2.into(), Some(record)).unwrap();
+        assert_eq!(user_word_count.word_count().word_count(), 3_000_000_000);
+    }
+
+    #[test]
+    pub fn test_user_word_count_from_invalid_record_fails()
+    {
+        let record = DbUserWordCount { word_count: -1 };
+        assert!(UserWordCount::from_record(1.into(), 2.into(), Some(record)).is_err());
+    }
+
+    #[test]
+    pub fn test_format_goal_progress()
+    {
+        assert_eq!(format_goal_progress(TotalWordCount(1500), TotalWordCount(5000)), "1,500 / 5,000 (30%)");
+        assert_eq!(format_goal_progress(TotalWordCount(0), TotalWordCount(50000)), "0 / 50,000 (0%)");
+        assert_eq!(format_goal_progress(TotalWordCount(4999), TotalWordCount(5000)), "4,999 / 5,000 (99%)");
+    }
+
+    #[test]
+    pub fn test_format_goal_progress_past_goal()
+    {
+        assert_eq!(format_goal_progress(TotalWordCount(7500), TotalWordCount(5000)), "7,500 / 5,000 (150%)");
+        assert_eq!(format_goal_progress(TotalWordCount(u32::MAX), TotalWordCount(1)), format!("4,294,967,295 / 1 ({}%)", u32::MAX as u64 * 100));
+    }
+
+    fn log_entry(word_count: u32, seconds: i64) -> WordCountLogEntry
+    {
+        WordCountLogEntry {
+            word_count: TotalWordCount(word_count),
+            created_at: DateTime::from_timestamp(seconds, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    pub fn test_history_deltas()
+    {
+        let entries = vec![log_entry(1200, 300), log_entry(1500, 200), log_entry(1000, 100)];
+        assert_eq!(history_deltas(&entries), vec![Some(-300), Some(500), None]);
+    }
+
+    #[test]
+    pub fn test_history_deltas_empty_and_single()
+    {
+        assert!(history_deltas(&[]).is_empty());
+        assert_eq!(history_deltas(&[log_entry(50, 100)]), vec![None]);
+    }
+
+    #[test]
+    pub fn test_words_since()
+    {
+        let entries = vec![log_entry(1800, 300), log_entry(1500, 200), log_entry(1000, 100)];
+        assert_eq!(words_since(&entries, DateTime::from_timestamp(150, 0).unwrap()), 800);
+        assert_eq!(words_since(&entries, DateTime::from_timestamp(300, 0).unwrap()), 300);
+    }
+
+    #[test]
+    pub fn test_words_since_only_old_entry_is_zero()
+    {
+        let entries = vec![log_entry(1000, 100)];
+        assert_eq!(words_since(&entries, DateTime::from_timestamp(200, 0).unwrap()), 0);
+        assert_eq!(words_since(&[], DateTime::from_timestamp(200, 0).unwrap()), 0);
+    }
+
+    #[test]
+    pub fn test_words_since_first_report_counts_from_zero()
+    {
+        let entries = vec![log_entry(700, 300), log_entry(500, 250)];
+        assert_eq!(words_since(&entries, DateTime::from_timestamp(200, 0).unwrap()), 700);
+    }
+
+    #[test]
+    pub fn test_words_since_counts_from_total_before_history()
+    {
+        // 50,000 words from before history was kept, then a report today.
+        let entries = vec![log_entry(50100, 300), log_entry(50000, 0)];
+        assert!(entries[1].is_from_before_history());
+        assert!(!entries[0].is_from_before_history());
+        assert_eq!(words_since(&entries, DateTime::from_timestamp(200, 0).unwrap()), 100);
+    }
+
+    #[test]
+    pub fn test_day_and_week_start()
+    {
+        // Thursday 2026-10-15 13:45:10 UTC
+        let now = DateTime::parse_from_rfc3339("2026-10-15T13:45:10Z").unwrap().to_utc();
+        let (day_start, week_start) = day_and_week_start(now);
+        assert_eq!(day_start, DateTime::parse_from_rfc3339("2026-10-15T00:00:00Z").unwrap().to_utc());
+        assert_eq!(week_start, DateTime::parse_from_rfc3339("2026-10-12T00:00:00Z").unwrap().to_utc());
+
+        // On a Monday the week starts today.
+        let now = DateTime::parse_from_rfc3339("2026-10-12T00:00:00Z").unwrap().to_utc();
+        assert_eq!(day_and_week_start(now), (now, now));
+    }
+}